@@ -0,0 +1,77 @@
+//! Micro-benchmarks for the hot math paths
+//!
+//! Run with `cargo bench`. Uses a plain timing loop so no extra dependencies are needed.
+//!
+//! The math deliberately avoids `f64::mul_add`: without hardware FMA (which the default target
+//! lacks) it compiles to a call to a much slower software routine. Compare builds with
+//! `RUSTFLAGS="-C target-cpu=native"`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use ray_tracer_challenge::*;
+
+const ITERATIONS: u32 = 10_000_000;
+
+fn bench<F: FnMut()>(name: &str, mut f: F) {
+    // Warm up
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{:<28} {:>8.2} ns/iter",
+        name,
+        elapsed.as_nanos() as f64 / ITERATIONS as f64
+    );
+}
+
+fn main() {
+    let a = Vector::new(1.0, 2.0, 3.0);
+    let b = Vector::new(4.0, 5.0, 6.0);
+
+    bench("vector dot", || {
+        black_box(black_box(a).dot(&black_box(b)));
+    });
+
+    bench("vector cross", || {
+        black_box(black_box(a).cross(&black_box(b)));
+    });
+
+    bench("vector normalize", || {
+        black_box(black_box(a).normalize());
+    });
+
+    let m = Matrix4::new([
+        [1.0, 2.0, 3.0, 4.0],
+        [4.0, 1.0, 2.0, 3.0],
+        [3.0, 4.0, 1.0, 2.0],
+        [2.0, 3.0, 4.0, 1.0],
+    ]);
+
+    bench("matrix4 mul", || {
+        black_box(black_box(m) * black_box(m));
+    });
+
+    let p = Point::new(1.0, 2.0, 3.0);
+
+    bench("matrix4 * point", || {
+        black_box(black_box(m) * black_box(p));
+    });
+
+    let c = Color::new(0.1, 0.2, 0.3);
+
+    bench("color accumulate (ops)", || {
+        black_box(black_box(c) * 0.5 + black_box(c));
+    });
+
+    bench("color accumulate (scaled_add)", || {
+        black_box(black_box(c).scaled_add(0.5, &black_box(c)));
+    });
+}
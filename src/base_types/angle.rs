@@ -0,0 +1,93 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Angle, stored in radians
+///
+/// Constructed explicitly from either unit, so degrees cannot be passed where radians are
+/// expected (or vice versa).
+///
+/// ```
+/// use std::f64::consts::PI;
+/// use ray_tracer_challenge::Angle;
+///
+/// assert_eq!(Angle::degrees(180.0), Angle::radians(PI));
+/// assert_eq!(Angle::radians(PI / 2.0).to_degrees(), 90.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, PartialOrd, Default, Debug)]
+pub struct Angle(f64);
+
+impl Angle {
+    pub const fn radians(radians: f64) -> Self {
+        Self(radians)
+    }
+
+    pub fn degrees(degrees: f64) -> Self {
+        Self(degrees.to_radians())
+    }
+
+    pub const fn to_radians(&self) -> f64 {
+        self.0
+    }
+
+    pub fn to_degrees(&self) -> f64 {
+        self.0.to_degrees()
+    }
+
+    #[inline]
+    pub fn sin_cos(&self) -> (f64, f64) {
+        self.0.sin_cos()
+    }
+
+    #[inline]
+    pub fn tan(&self) -> f64 {
+        self.0.tan()
+    }
+}
+
+// Negation
+impl Neg for &Angle {
+    type Output = Angle;
+
+    #[inline]
+    fn neg(self) -> Angle {
+        Angle(-self.0)
+    }
+}
+
+forward_ref_unop!(impl Neg, neg for Angle => Angle);
+
+// Angle and angle addition
+impl Add for &Angle {
+    type Output = Angle;
+
+    #[inline]
+    fn add(self, rhs: &Angle) -> Angle {
+        Angle(self.0 + rhs.0)
+    }
+}
+
+forward_ref_binop!(impl Add, add for Angle, Angle => Angle);
+
+// Angle and angle subtraction
+impl Sub for &Angle {
+    type Output = Angle;
+
+    #[inline]
+    fn sub(self, rhs: &Angle) -> Angle {
+        Angle(self.0 - rhs.0)
+    }
+}
+
+forward_ref_binop!(impl Sub, sub for Angle, Angle => Angle);
+
+// Angle and scalar multiplication
+impl Mul<&f64> for &Angle {
+    type Output = Angle;
+
+    #[inline]
+    fn mul(self, rhs: &f64) -> Angle {
+        Angle(self.0 * rhs)
+    }
+}
+
+forward_ref_binop!(impl Mul, mul for Angle, f64 => Angle);
+commutative_binop!(impl Mul, mul for Angle, f64 => Angle);
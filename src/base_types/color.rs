@@ -1,10 +1,23 @@
+use std::cmp::Ordering;
 use std::fmt::{Display, Error as FmtError, Formatter};
 
-use auto_ops::*;
+use std::ops::{Add, Mul, Sub};
 
-use super::{Point, Vector};
+use super::{Point, Rgb8, Vector};
 
-coordinate_struct!(Color, r, g, b);
+coordinate_struct!(
+    /// Linear RGB color, nominally in `[0, 1]` per channel but unbounded while accumulating light
+    ///
+    /// ```
+    /// use ray_tracer_challenge::{Color, Rgb8, WHITE};
+    ///
+    /// let c = Color::new(1.0, 0.5, 0.0) * WHITE;
+    ///
+    /// assert_eq!(Rgb8::from(c), Rgb8::new(255, 127, 0));
+    /// assert_eq!(c.to_string(), "255 127 0");
+    /// ```
+    Color, r, g, b
+);
 
 coordinate_struct_convert!(Color, Point, x, y, z);
 coordinate_struct_convert!(Color, Vector, x, y, z);
@@ -16,6 +29,56 @@ impl Color {
     pub fn gray(value: f64) -> Self {
         Self::new(value, value, value)
     }
+
+    /// Relative luminance (Rec. 709 weights)
+    #[inline]
+    pub fn luminance(&self) -> f64 {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+
+    /// `self * a + b`, for accumulating weighted samples into a color (not fused, unlike
+    /// `f64::mul_add`)
+    #[inline]
+    pub fn scaled_add(&self, a: f64, b: &Color) -> Self {
+        let r = self.r * a + b.r;
+        let g = self.g * a + b.g;
+        let b = self.b * a + b.b;
+
+        Self::new(r, g, b)
+    }
+}
+
+/// Color wrapper with a total, lexicographic (red, green, blue) ordering, for sorting and using
+/// colors as ordered keys
+#[derive(Copy, Clone, Debug)]
+pub struct OrderedColor(pub Color);
+
+impl PartialEq for OrderedColor {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedColor {}
+
+impl PartialOrd for OrderedColor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedColor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let [r, g, b] = self.0.componentwise_cmp(&other.0);
+
+        r.then(g).then(b)
+    }
+}
+
+impl From<Color> for OrderedColor {
+    fn from(color: Color) -> Self {
+        Self(color)
+    }
 }
 
 impl Default for Color {
@@ -26,44 +89,69 @@ impl Default for Color {
 
 impl Display for Color {
     fn fmt(&self, f: &mut Formatter) -> Result<(), FmtError> {
-        let r = ((255.0 * self.r) as u64).clamp(0, 255);
-        let g = ((255.0 * self.g) as u64).clamp(0, 255);
-        let b = ((255.0 * self.b) as u64).clamp(0, 255);
-
-        Ok(write!(f, "{} {} {}", r, g, b)?)
+        Rgb8::from(*self).fmt(f)
     }
 }
 
 // Color and color addition
-impl_op_ex!(+|lhs: &Color, rhs: &Color| -> Color {
-    let r = lhs.r + rhs.r;
-    let g = lhs.g + rhs.g;
-    let b = lhs.b + rhs.b;
+impl Add for &Color {
+    type Output = Color;
+
+    #[inline]
+    fn add(self, rhs: &Color) -> Color {
+        let r = self.r + rhs.r;
+        let g = self.g + rhs.g;
+        let b = self.b + rhs.b;
 
-    Color::new(r, g, b)
-});
+        Color::new(r, g, b)
+    }
+}
+
+forward_ref_binop!(impl Add, add for Color, Color => Color);
 
 // Color and color subtraction
-impl_op_ex!(-|lhs: &Color, rhs: &Color| -> Color {
-    let r = lhs.r - rhs.r;
-    let g = lhs.g - rhs.g;
-    let b = lhs.b - rhs.b;
+impl Sub for &Color {
+    type Output = Color;
+
+    #[inline]
+    fn sub(self, rhs: &Color) -> Color {
+        let r = self.r - rhs.r;
+        let g = self.g - rhs.g;
+        let b = self.b - rhs.b;
+
+        Color::new(r, g, b)
+    }
+}
 
-    Color::new(r, g, b)
-});
+forward_ref_binop!(impl Sub, sub for Color, Color => Color);
 
 // Color and color multiplication (Hadamard/element-wise product)
-impl_op_ex!(*|lhs: &Color, rhs: &Color| -> Color {
-    let r = lhs.r * rhs.r;
-    let g = lhs.g * rhs.g;
-    let b = lhs.b * rhs.b;
+impl Mul for &Color {
+    type Output = Color;
+
+    #[inline]
+    fn mul(self, rhs: &Color) -> Color {
+        let r = self.r * rhs.r;
+        let g = self.g * rhs.g;
+        let b = self.b * rhs.b;
+
+        Color::new(r, g, b)
+    }
+}
 
-    Color::new(r, g, b)
-});
+forward_ref_binop!(impl Mul, mul for Color, Color => Color);
 
 // Color and scalar multiplication
-impl_op_ex_commutative!(*|lhs: &Color, rhs: &f64| -> Color {
-    let rhs = Color::new(*rhs, *rhs, *rhs);
+impl Mul<&f64> for &Color {
+    type Output = Color;
+
+    #[inline]
+    fn mul(self, rhs: &f64) -> Color {
+        let rhs = Color::new(*rhs, *rhs, *rhs);
+
+        self * rhs
+    }
+}
 
-    lhs * rhs
-});
+forward_ref_binop!(impl Mul, mul for Color, f64 => Color);
+commutative_binop!(impl Mul, mul for Color, f64 => Color);
@@ -0,0 +1,68 @@
+//! Conversion between left-handed (as used by the book) and right-handed (OBJ, glTF) coordinates
+//!
+//! Converting mirrors the z axis, which is its own inverse, so the same helpers convert in either
+//! direction. Mirroring also reverses triangle winding order, which mesh importers must account
+//! for separately.
+
+use super::{Point, Transform, Vector};
+
+impl Point {
+    /// The same point expressed in the opposite-handed coordinate system
+    pub fn flip_handedness(&self) -> Self {
+        Self::new(self.x, self.y, -self.z)
+    }
+}
+
+impl Vector {
+    /// The same vector expressed in the opposite-handed coordinate system
+    pub fn flip_handedness(&self) -> Self {
+        Self::new(self.x, self.y, -self.z)
+    }
+}
+
+impl Transform {
+    /// The same transformation expressed in the opposite-handed coordinate system
+    ///
+    /// Conjugates by the z mirror, so that converting a point and then transforming it gives the
+    /// converted result of transforming the original point.
+    pub fn flip_handedness(&self) -> Self {
+        let mirror = Transform::scaling(1.0, 1.0, -1.0);
+
+        mirror * self * mirror
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Angle, Point, Transform, Vector};
+
+    #[test]
+    fn flipping_twice_is_identity() {
+        let p = Point::new(1.0, 2.0, 3.0);
+        let v = Vector::new(-1.0, 0.5, 4.0);
+
+        assert_eq!(p.flip_handedness(), Point::new(1.0, 2.0, -3.0));
+        assert_eq!(p.flip_handedness().flip_handedness(), p);
+        assert_eq!(v.flip_handedness().flip_handedness(), v);
+    }
+
+    #[test]
+    fn flipped_transform_commutes_with_conversion() {
+        let t = Transform::rotation_y(Angle::degrees(30.0)).then(&Transform::translation(1.0, 2.0, 3.0));
+        let p = Point::new(4.0, -5.0, 6.0);
+
+        let a = t.flip_handedness() * p.flip_handedness();
+        let b = (t * p).flip_handedness();
+
+        assert!((a - b).length() < 1e-12);
+    }
+
+    #[test]
+    fn flipped_rotation_turns_the_other_way() {
+        let t = Transform::rotation_y(Angle::degrees(90.0)).flip_handedness();
+        let r = Transform::rotation_y(Angle::degrees(-90.0));
+        let p = Point::new(1.0, 0.0, 0.0);
+
+        assert!((t * p - r * p).length() < 1e-12);
+    }
+}
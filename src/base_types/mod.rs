@@ -1,9 +1,10 @@
-/// Base types for the ray tracer
+//! Base types for the ray tracer
 
 // Utility to define a three coordinate struct
 macro_rules! coordinate_struct {
-    ($struct:tt, $x:tt, $y:tt, $z:tt) => {
-        #[derive(Copy, Clone, PartialOrd, PartialEq, Debug)]
+    ($(#[$attr:meta])* $struct:ident, $x:tt, $y:tt, $z:tt) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, PartialEq, Debug)]
         pub struct $struct {
             pub $x: f64,
             pub $y: f64,
@@ -22,6 +23,27 @@ macro_rules! coordinate_struct {
             pub const fn one() -> Self {
                 Self::new(1.0, 1.0, 1.0)
             }
+
+            /// Component-wise minimum
+            #[inline]
+            pub fn min(&self, other: &Self) -> Self {
+                Self::new(self.$x.min(other.$x), self.$y.min(other.$y), self.$z.min(other.$z))
+            }
+
+            /// Component-wise maximum
+            #[inline]
+            pub fn max(&self, other: &Self) -> Self {
+                Self::new(self.$x.max(other.$x), self.$y.max(other.$y), self.$z.max(other.$z))
+            }
+
+            /// Component-wise (total) ordering of each coordinate against `other`
+            pub fn componentwise_cmp(&self, other: &Self) -> [std::cmp::Ordering; 3] {
+                [
+                    self.$x.total_cmp(&other.$x),
+                    self.$y.total_cmp(&other.$y),
+                    self.$z.total_cmp(&other.$z),
+                ]
+            }
         }
     };
 }
@@ -37,15 +59,86 @@ macro_rules! coordinate_struct_convert {
     };
 }
 
+// Utility to implement the owned variant of a unary operator from its `&T` implementation
+macro_rules! forward_ref_unop {
+    (impl $imp:ident, $method:ident for $t:ty => $out:ty) => {
+        impl std::ops::$imp for $t {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self) -> $out {
+                std::ops::$imp::$method(&self)
+            }
+        }
+    };
+}
+
+// Utility to implement the owned/borrowed variants of a binary operator from its `&L op &R`
+// implementation
+macro_rules! forward_ref_binop {
+    (impl $imp:ident, $method:ident for $lhs:ty, $rhs:ty => $out:ty) => {
+        impl std::ops::$imp<$rhs> for $lhs {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, rhs: $rhs) -> $out {
+                std::ops::$imp::$method(&self, &rhs)
+            }
+        }
+
+        impl std::ops::$imp<&$rhs> for $lhs {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, rhs: &$rhs) -> $out {
+                std::ops::$imp::$method(&self, rhs)
+            }
+        }
+
+        impl std::ops::$imp<$rhs> for &$lhs {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, rhs: $rhs) -> $out {
+                std::ops::$imp::$method(self, &rhs)
+            }
+        }
+    };
+}
+
+// Utility to implement the swapped-operand variants of a commutative binary operator, given the
+// `&L op &R` implementation
+macro_rules! commutative_binop {
+    (impl $imp:ident, $method:ident for $lhs:ty, $rhs:ty => $out:ty) => {
+        impl std::ops::$imp<&$lhs> for &$rhs {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, rhs: &$lhs) -> $out {
+                std::ops::$imp::$method(rhs, self)
+            }
+        }
+
+        forward_ref_binop!(impl $imp, $method for $rhs, $lhs => $out);
+    };
+}
+
+mod angle;
 mod color;
+mod handedness;
 mod point;
+mod rgb8;
 mod vector;
 mod tiny_matrix;
+mod transform;
 
+pub use angle::*;
 pub use color::*;
 pub use point::*;
+pub use rgb8::*;
 pub use vector::*;
 pub use tiny_matrix::*;
+pub use transform::*;
 
 #[cfg(test)]
 mod tests {
@@ -59,7 +152,7 @@ mod tests {
     mod point_vector_color {
         use crate::{Color, Point, Vector};
 
-        use std::f64::{consts::PI, EPSILON};
+        use std::f64::consts::PI;
 
         #[test]
         fn adding_two_vectors() {
@@ -70,6 +163,20 @@ mod tests {
             assert_eq_commutative!(+, A, B, R);
         }
 
+        #[test]
+        #[allow(clippy::op_ref)]
+        fn operators_on_references() {
+            const A: Vector = Vector::new(3.0, 2.0, 1.0);
+            const B: Vector = Vector::new(5.0, 6.0, 7.0);
+            const R: Vector = Vector::new(8.0, 8.0, 8.0);
+
+            assert_eq!(&A + &B, R);
+            assert_eq!(A + &B, R);
+            assert_eq!(&A + B, R);
+            assert_eq!(-&A, -A);
+            assert_eq!(&2.0 * &A, A * 2.0);
+        }
+
         #[test]
         fn adding_a_point_and_a_vector() {
             const A: Point = Point::new(3.0, 2.0, 1.0);
@@ -130,6 +237,14 @@ mod tests {
             assert_eq!(A / 2.0, R);
         }
 
+        #[test]
+        fn broadcast_adding_a_scalar_to_a_vector() {
+            const A: Vector = Vector::new(1.0, -2.0, 3.0);
+            const R: Vector = Vector::new(1.5, -1.5, 3.5);
+
+            assert_eq!(A.broadcast_add(0.5), R);
+        }
+
         #[test]
         fn length_of_a_vector() {
             const A: Vector = Vector::new(-1.0, 2.0, -3.0);
@@ -162,6 +277,7 @@ mod tests {
         }
 
         #[test]
+        #[allow(clippy::float_equality_without_abs)]
         fn dot_product_of_unit_vectors_is_cos_of_their_angle() {
             assert!({
                 // 10:30 and 9:30, theta is 90 degrees
@@ -169,7 +285,7 @@ mod tests {
                 let b = Vector::new(-1.0, 1.0, 0.0).normalize();
                 let theta = (PI / 2.0).cos();
 
-                a.dot(&b) - theta < EPSILON
+                a.dot(&b) - theta < f64::EPSILON
             });
 
             assert!({
@@ -178,7 +294,7 @@ mod tests {
                 let b = Vector::new(-1.0, 0.0, 0.0);
                 let theta = (PI * 3.0 / 2.0).cos();
 
-                a.dot(&b) - theta < EPSILON
+                a.dot(&b) - theta < f64::EPSILON
             });
         }
 
@@ -192,8 +308,38 @@ mod tests {
             assert_eq!(A.cross(&B), R);
             assert_eq!(B.cross(&A), -R);
 
-            assert!(A.dot(&R) - EPSILON < 0.0);
-            assert!(B.dot(&R) - EPSILON < 0.0);
+            assert!(A.dot(&R) - f64::EPSILON < 0.0);
+            assert!(B.dot(&R) - f64::EPSILON < 0.0);
+        }
+
+        #[test]
+        fn componentwise_min_max_and_cmp() {
+            use std::cmp::Ordering::*;
+
+            const A: Point = Point::new(1.0, 5.0, -2.0);
+            const B: Point = Point::new(3.0, 5.0, -4.0);
+
+            assert_eq!(A.min(&B), Point::new(1.0, 5.0, -4.0));
+            assert_eq!(A.max(&B), Point::new(3.0, 5.0, -2.0));
+            assert_eq!(A.componentwise_cmp(&B), [Less, Equal, Greater]);
+        }
+
+        #[test]
+        fn sorting_ordered_colors() {
+            use crate::OrderedColor;
+
+            let mut colors = vec![
+                OrderedColor(Color::new(0.5, 0.0, 0.0)),
+                OrderedColor(Color::new(0.1, 0.9, 0.0)),
+                OrderedColor(Color::new(0.1, 0.2, 0.3)),
+            ];
+            colors.sort();
+
+            assert_eq!(colors, vec![
+                OrderedColor(Color::new(0.1, 0.2, 0.3)),
+                OrderedColor(Color::new(0.1, 0.9, 0.0)),
+                OrderedColor(Color::new(0.5, 0.0, 0.0)),
+            ]);
         }
 
         #[test]
@@ -204,6 +350,7 @@ mod tests {
             assert_eq_commutative!(*, A, A, Color::new(1.0, 4.0, 9.0));
             assert_eq_commutative!(*, A, 3.0, Color::new(3.0, 6.0, 9.0));
             assert_eq!(A - 0.5 * A, Color::new(0.5, 1.0, 1.5));
+            assert_eq!(A.scaled_add(2.0, &A), A * 3.0);
         }
     }
 
@@ -547,4 +694,127 @@ mod tests {
             assert!(res.iter().all(|v| v.abs() < EPSILON));
         }
     }
+
+    // Invariants checked over many pseudo-randomly generated inputs, rather than hand-picked
+    // examples. The generator is seeded so failures are reproducible.
+    mod properties {
+        use crate::{Matrix2, Matrix3, Matrix4, Point, Vector};
+
+        const CASES: usize = 1000;
+
+        // xorshift64* generator
+        struct Rng(u64);
+
+        impl Rng {
+            fn new() -> Self {
+                Rng(0x9e37_79b9_7f4a_7c15)
+            }
+
+            // Uniform in [-10, 10)
+            fn next_f64(&mut self) -> f64 {
+                self.0 ^= self.0 >> 12;
+                self.0 ^= self.0 << 25;
+                self.0 ^= self.0 >> 27;
+                let bits = self.0.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 11;
+
+                bits as f64 / (1u64 << 53) as f64 * 20.0 - 10.0
+            }
+
+            fn vector(&mut self) -> Vector {
+                Vector::new(self.next_f64(), self.next_f64(), self.next_f64())
+            }
+
+            fn point(&mut self) -> Point {
+                Point::new(self.next_f64(), self.next_f64(), self.next_f64())
+            }
+        }
+
+        macro_rules! random_matrix {
+            ($rng:expr, $matrix:tt) => {{
+                let mut m = $matrix::zero();
+                m.iter_mut().for_each(|v| *v = $rng.next_f64());
+                m
+            }};
+        }
+
+        fn approx_eq(a: f64, b: f64) -> bool {
+            (a - b).abs() <= 1e-9 * (1.0 + a.abs().max(b.abs()))
+        }
+
+        macro_rules! matrix_properties {
+            ($name:ident, $matrix:tt) => {
+                #[test]
+                fn $name() {
+                    let mut rng = Rng::new();
+
+                    for _ in 0..CASES {
+                        let a = random_matrix!(rng, $matrix);
+                        let b = random_matrix!(rng, $matrix);
+
+                        // (AB)^T = B^T A^T
+                        let lhs = (a * b).transposed();
+                        let rhs = b.transposed() * a.transposed();
+                        assert!(lhs.iter().zip(rhs.iter()).all(|(&l, &r)| approx_eq(l, r)));
+
+                        // Transposing twice is the identity
+                        assert_eq!(a.transposed().transposed(), a);
+
+                        // A A^-1 = I, for reasonably conditioned matrices
+                        if a.det().abs() > 1e-3 {
+                            let res = a * a.inverse().unwrap();
+                            let ident = $matrix::ident();
+                            assert!(res.iter().zip(ident.iter()).all(|(&l, &r)| (l - r).abs() < 1e-6));
+                        }
+                    }
+                }
+            };
+        }
+
+        matrix_properties!(matrix2_invariants, Matrix2);
+        matrix_properties!(matrix3_invariants, Matrix3);
+        matrix_properties!(matrix4_invariants, Matrix4);
+
+        #[test]
+        fn normalizing_preserves_direction() {
+            let mut rng = Rng::new();
+
+            for _ in 0..CASES {
+                let v = rng.vector();
+                let n = v.normalize();
+
+                assert!(approx_eq(n.length(), 1.0));
+                assert!(n.dot(&v) > 0.0);
+                assert!(n.cross(&v).length() < 1e-9 * v.length());
+            }
+        }
+
+        #[test]
+        fn cross_product_is_orthogonal_and_anticommutative() {
+            let mut rng = Rng::new();
+
+            for _ in 0..CASES {
+                let a = rng.vector();
+                let b = rng.vector();
+                let c = a.cross(&b);
+
+                assert_eq!(b.cross(&a), -c);
+                assert!(c.dot(&a).abs() < 1e-9 * (1.0 + c.length() * a.length()));
+                assert!(c.dot(&b).abs() < 1e-9 * (1.0 + c.length() * b.length()));
+            }
+        }
+
+        #[test]
+        fn moving_a_point_and_back_is_identity() {
+            let mut rng = Rng::new();
+
+            for _ in 0..CASES {
+                let p = rng.point();
+                let v = rng.vector();
+                let q = p + v - v;
+
+                assert!(approx_eq(p.x, q.x) && approx_eq(p.y, q.y) && approx_eq(p.z, q.z));
+                assert_eq!((p + v) - p, p.to(&(p + v)));
+            }
+        }
+    }
 }
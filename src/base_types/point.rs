@@ -1,9 +1,21 @@
-use auto_ops::*;
+use std::ops::{Add, Sub};
 
 use super::Vector;
 
-// Point
-coordinate_struct!(Point, x, y, z);
+coordinate_struct!(
+    /// Position in space
+    ///
+    /// ```
+    /// use ray_tracer_challenge::{Point, Vector};
+    ///
+    /// let p = Point::new(1.0, 2.0, 3.0);
+    /// let q = p + Vector::new(1.0, 0.0, 0.0);
+    ///
+    /// assert_eq!(q, Point::new(2.0, 2.0, 3.0));
+    /// assert_eq!(p.to(&q), Vector::new(1.0, 0.0, 0.0));
+    /// ```
+    Point, x, y, z
+);
 
 // From vector to point
 coordinate_struct_convert!(Point, Vector, x, y, z);
@@ -15,24 +27,45 @@ impl Point {
 }
 
 // Point and vector addition (move a point)
-impl_op_ex_commutative!(+|lhs: &Point, rhs: &Vector| -> Point {
-    let lhs = Vector::from(*lhs);
+impl Add<&Vector> for &Point {
+    type Output = Point;
 
-    Point::from(lhs + rhs)
-});
+    #[inline]
+    fn add(self, rhs: &Vector) -> Point {
+        let lhs = Vector::from(*self);
+
+        Point::from(lhs + rhs)
+    }
+}
+
+forward_ref_binop!(impl Add, add for Point, Vector => Point);
+commutative_binop!(impl Add, add for Point, Vector => Point);
 
 // Point and vector subtraction (move a point)
-impl_op_ex!(-|lhs: &Point, rhs: &Vector| -> Point {
-    let rhs = -rhs;
+impl Sub<&Vector> for &Point {
+    type Output = Point;
 
-    lhs + rhs
-});
+    #[inline]
+    fn sub(self, rhs: &Vector) -> Point {
+        let rhs = -rhs;
+
+        self + rhs
+    }
+}
+
+forward_ref_binop!(impl Sub, sub for Point, Vector => Point);
 
 // Point and point subtraction (vector between points)
-impl_op_ex!(-|lhs: &Point, rhs: &Point| -> Vector {
-    let lhs = Vector::from(*lhs);
-    let rhs = Vector::from(*rhs);
+impl Sub for &Point {
+    type Output = Vector;
+
+    #[inline]
+    fn sub(self, rhs: &Point) -> Vector {
+        let lhs = Vector::from(*self);
+        let rhs = Vector::from(*rhs);
 
-    lhs - rhs
-});
+        lhs - rhs
+    }
+}
 
+forward_ref_binop!(impl Sub, sub for Point, Point => Vector);
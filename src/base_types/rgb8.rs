@@ -0,0 +1,75 @@
+use std::fmt::{Display, Error as FmtError, Formatter};
+
+use super::Color;
+
+/// Quantized 8-bit per channel color
+///
+/// Unlike [`Color`] this is `Eq + Hash`, so it can be used for palettes, exact image comparisons
+/// and as a map key.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Rgb8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb8 {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+// Quantize a single channel, clamping to the displayable range
+fn quantize(value: f64) -> u8 {
+    ((255.0 * value) as u64).clamp(0, 255) as u8
+}
+
+impl From<Color> for Rgb8 {
+    fn from(color: Color) -> Self {
+        Self::new(quantize(color.r), quantize(color.g), quantize(color.b))
+    }
+}
+
+impl From<Rgb8> for Color {
+    fn from(color: Rgb8) -> Self {
+        let r = color.r as f64 / 255.0;
+        let g = color.g as f64 / 255.0;
+        let b = color.b as f64 / 255.0;
+
+        Color::new(r, g, b)
+    }
+}
+
+impl Display for Rgb8 {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FmtError> {
+        write!(f, "{} {} {}", self.r, self.g, self.b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{Color, Rgb8, BLACK, WHITE};
+
+    #[test]
+    fn quantizing_clamps_to_range() {
+        assert_eq!(Rgb8::from(Color::new(-0.5, 0.5, 1.5)), Rgb8::new(0, 127, 255));
+        assert_eq!(Rgb8::from(BLACK), Rgb8::new(0, 0, 0));
+        assert_eq!(Rgb8::from(WHITE), Rgb8::new(255, 255, 255));
+    }
+
+    #[test]
+    fn round_trip_through_color() {
+        let c = Rgb8::new(12, 34, 56);
+
+        assert_eq!(Rgb8::from(Color::from(c)), c);
+    }
+
+    #[test]
+    fn usable_as_hash_key() {
+        let set: HashSet<Rgb8> = vec![WHITE, BLACK, WHITE].into_iter().map(Rgb8::from).collect();
+
+        assert_eq!(set.len(), 2);
+    }
+}
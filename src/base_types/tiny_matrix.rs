@@ -1,5 +1,7 @@
 use std::ops::{Neg, Add, Mul, Sub, Div};
 
+use super::{Point, Vector};
+
 macro_rules! matrix {
     // (Internal match) Matrix definition and implementation
     // - `matrix`: Matrix type name
@@ -95,13 +97,14 @@ macro_rules! matrix {
         impl Mul for $matrix {
             type Output = $matrix;
 
+            #[inline]
             fn mul(self, rhs: $matrix) -> Self::Output {
                 let mut res = $matrix::zero();
 
                 for r in 0..self.d {
                     for c in 0..self.d {
                         for i in 0..self.d {
-                            res.data[r][c] += (self.data[r][i] * rhs.data[i][c]);
+                            res.data[r][c] += self.data[r][i] * rhs.data[i][c];
                         }
                     }
                 }
@@ -204,3 +207,37 @@ matrix!(Matrix1, 1);
 matrix!(Matrix2, 2, Matrix1);
 matrix!(Matrix3, 3, Matrix2);
 matrix!(Matrix4, 4, Matrix3);
+
+// Matrix and point multiplication (w = 1, translation applies)
+impl Mul<&Point> for &Matrix4 {
+    type Output = Point;
+
+    #[inline]
+    fn mul(self, rhs: &Point) -> Point {
+        let m = &self.data;
+        let x = m[0][0] * rhs.x + m[0][1] * rhs.y + m[0][2] * rhs.z + m[0][3];
+        let y = m[1][0] * rhs.x + m[1][1] * rhs.y + m[1][2] * rhs.z + m[1][3];
+        let z = m[2][0] * rhs.x + m[2][1] * rhs.y + m[2][2] * rhs.z + m[2][3];
+
+        Point::new(x, y, z)
+    }
+}
+
+forward_ref_binop!(impl Mul, mul for Matrix4, Point => Point);
+
+// Matrix and vector multiplication (w = 0, translation is ignored)
+impl Mul<&Vector> for &Matrix4 {
+    type Output = Vector;
+
+    #[inline]
+    fn mul(self, rhs: &Vector) -> Vector {
+        let m = &self.data;
+        let x = m[0][0] * rhs.x + m[0][1] * rhs.y + m[0][2] * rhs.z;
+        let y = m[1][0] * rhs.x + m[1][1] * rhs.y + m[1][2] * rhs.z;
+        let z = m[2][0] * rhs.x + m[2][1] * rhs.y + m[2][2] * rhs.z;
+
+        Vector::new(x, y, z)
+    }
+}
+
+forward_ref_binop!(impl Mul, mul for Matrix4, Vector => Vector);
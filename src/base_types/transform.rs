@@ -0,0 +1,373 @@
+use std::ops::Mul;
+
+use super::{Angle, Matrix4, Point, Vector};
+
+/// Invertible affine transformation
+///
+/// Wraps a [`Matrix4`] together with its inverse, which is computed once on construction. Since a
+/// `Transform` can only be built from an invertible matrix, shapes and cameras holding one never
+/// have to deal with a singular transformation.
+///
+/// ```
+/// use ray_tracer_challenge::{Point, Transform};
+///
+/// let t = Transform::translation(5.0, -3.0, 2.0).then(&Transform::scaling(2.0, 2.0, 2.0));
+///
+/// assert_eq!(t * Point::new(1.0, 1.0, 1.0), Point::new(12.0, -4.0, 6.0));
+/// assert_eq!(t.inverted() * Point::new(12.0, -4.0, 6.0), Point::new(1.0, 1.0, 1.0));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Transform {
+    matrix: Matrix4,
+    inverse: Matrix4,
+}
+
+impl Transform {
+    /// Transformation from a matrix, or `None` if the matrix is not invertible or not finite
+    pub fn new(matrix: Matrix4) -> Option<Self> {
+        if !matrix.iter().all(|v| v.is_finite()) || !matrix.det().is_finite() {
+            return None;
+        }
+
+        let inverse = matrix.inverse()?;
+
+        Some(Self { matrix, inverse })
+    }
+
+    // Transformation from a matrix and its known inverse
+    const fn with_inverse(matrix: Matrix4, inverse: Matrix4) -> Self {
+        Self { matrix, inverse }
+    }
+
+    pub fn identity() -> Self {
+        Self::with_inverse(Matrix4::ident(), Matrix4::ident())
+    }
+
+    /// Translation by each component
+    ///
+    /// # Panics
+    ///
+    /// If any of the components is not finite.
+    pub fn translation(x: f64, y: f64, z: f64) -> Self {
+        assert!(x.is_finite() && y.is_finite() && z.is_finite(), "translation must be finite");
+
+        let mut m = Matrix4::ident();
+        m.data[0][3] = x;
+        m.data[1][3] = y;
+        m.data[2][3] = z;
+
+        let mut inv = Matrix4::ident();
+        inv.data[0][3] = -x;
+        inv.data[1][3] = -y;
+        inv.data[2][3] = -z;
+
+        Self::with_inverse(m, inv)
+    }
+
+    /// Scaling along each axis
+    ///
+    /// # Panics
+    ///
+    /// If any of the factors is zero, as the transformation would not be invertible, or if any of
+    /// the factors or their reciprocals is not finite.
+    pub fn scaling(x: f64, y: f64, z: f64) -> Self {
+        assert!(x != 0.0 && y != 0.0 && z != 0.0, "scaling by zero is not invertible");
+        assert!(
+            [x, y, z].iter().all(|v| v.is_finite() && v.recip().is_finite()),
+            "scaling must be finite, with a finite inverse"
+        );
+
+        let mut m = Matrix4::ident();
+        m.data[0][0] = x;
+        m.data[1][1] = y;
+        m.data[2][2] = z;
+
+        let mut inv = Matrix4::ident();
+        inv.data[0][0] = 1.0 / x;
+        inv.data[1][1] = 1.0 / y;
+        inv.data[2][2] = 1.0 / z;
+
+        Self::with_inverse(m, inv)
+    }
+
+    // Rotation from the upper-left 3x3 block (the inverse of a rotation is its transpose)
+    fn rotation_from(r: [[f64; 3]; 3]) -> Self {
+        let mut m = Matrix4::ident();
+        for (row, src) in m.data.iter_mut().zip(r.iter()) {
+            row[..3].copy_from_slice(src);
+        }
+
+        Self::with_inverse(m, m.transposed())
+    }
+
+    /// Rotation by `angle` around the x axis
+    pub fn rotation_x(angle: Angle) -> Self {
+        let (s, c) = angle.sin_cos();
+
+        Self::rotation_from([
+            [1.0, 0.0, 0.0],
+            [0.0, c, -s],
+            [0.0, s, c],
+        ])
+    }
+
+    /// Rotation by `angle` around the y axis
+    pub fn rotation_y(angle: Angle) -> Self {
+        let (s, c) = angle.sin_cos();
+
+        Self::rotation_from([
+            [c, 0.0, s],
+            [0.0, 1.0, 0.0],
+            [-s, 0.0, c],
+        ])
+    }
+
+    /// Rotation by `angle` around the z axis
+    pub fn rotation_z(angle: Angle) -> Self {
+        let (s, c) = angle.sin_cos();
+
+        Self::rotation_from([
+            [c, -s, 0.0],
+            [s, c, 0.0],
+            [0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Rotation by `angle` around an arbitrary axis through the origin
+    ///
+    /// The axis is normalized, so it does not need to be a unit vector.
+    ///
+    /// # Panics
+    ///
+    /// If the axis is zero, as it has no direction to rotate around.
+    pub fn rotation(axis: &Vector, angle: Angle) -> Self {
+        assert!(axis.length_squared() > 0.0, "rotation axis must not be zero");
+        let k = axis.normalize();
+        let (s, c) = angle.sin_cos();
+        let t = 1.0 - c;
+
+        Self::rotation_from([
+            [t * k.x * k.x + c, t * k.x * k.y - s * k.z, t * k.x * k.z + s * k.y],
+            [t * k.x * k.y + s * k.z, t * k.y * k.y + c, t * k.y * k.z - s * k.x],
+            [t * k.x * k.z - s * k.y, t * k.y * k.z + s * k.x, t * k.z * k.z + c],
+        ])
+    }
+
+    /// Shearing, each component moved in proportion to the other two
+    ///
+    /// # Panics
+    ///
+    /// If the resulting transformation is not invertible.
+    pub fn shearing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Self {
+        let m = Matrix4::new([
+            [1.0, xy, xz, 0.0],
+            [yx, 1.0, yz, 0.0],
+            [zx, zy, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+
+        Self::new(m).expect("shearing is not invertible")
+    }
+
+    pub fn matrix(&self) -> &Matrix4 {
+        &self.matrix
+    }
+
+    pub fn inverse(&self) -> &Matrix4 {
+        &self.inverse
+    }
+
+    /// The inverse transformation
+    pub fn inverted(&self) -> Self {
+        Self::with_inverse(self.inverse, self.matrix)
+    }
+
+    /// This transformation followed by `next`
+    pub fn then(&self, next: &Transform) -> Self {
+        next * self
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+// Transform composition (right-hand side applied first)
+impl Mul for &Transform {
+    type Output = Transform;
+
+    #[inline]
+    fn mul(self, rhs: &Transform) -> Transform {
+        Transform::with_inverse(self.matrix * rhs.matrix, rhs.inverse * self.inverse)
+    }
+}
+
+forward_ref_binop!(impl Mul, mul for Transform, Transform => Transform);
+
+// Transform a point
+impl Mul<&Point> for &Transform {
+    type Output = Point;
+
+    #[inline]
+    fn mul(self, rhs: &Point) -> Point {
+        self.matrix * rhs
+    }
+}
+
+forward_ref_binop!(impl Mul, mul for Transform, Point => Point);
+
+// Transform a vector
+impl Mul<&Vector> for &Transform {
+    type Output = Vector;
+
+    #[inline]
+    fn mul(self, rhs: &Vector) -> Vector {
+        self.matrix * rhs
+    }
+}
+
+forward_ref_binop!(impl Mul, mul for Transform, Vector => Vector);
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, SQRT_2};
+
+    use crate::{Angle, Matrix4, Point, Transform, Vector};
+
+    const QUARTER: Angle = Angle::radians(FRAC_PI_2);
+    const EIGHTH: Angle = Angle::radians(FRAC_PI_4);
+
+    const EPSILON: f64 = 1.0e-12;
+
+    fn assert_point_eq(a: Point, b: Point) {
+        assert!((a - b).length() < EPSILON, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn translating_points_but_not_vectors() {
+        let t = Transform::translation(5.0, -3.0, 2.0);
+
+        assert_eq!(t * Point::new(-3.0, 4.0, 5.0), Point::new(2.0, 1.0, 7.0));
+        assert_eq!(t.inverted() * Point::new(-3.0, 4.0, 5.0), Point::new(-8.0, 7.0, 3.0));
+        assert_eq!(t * Vector::new(-3.0, 4.0, 5.0), Vector::new(-3.0, 4.0, 5.0));
+    }
+
+    #[test]
+    fn scaling_and_reflection() {
+        let t = Transform::scaling(2.0, 3.0, 4.0);
+
+        assert_eq!(t * Point::new(-4.0, 6.0, 8.0), Point::new(-8.0, 18.0, 32.0));
+        assert_eq!(t * Vector::new(-4.0, 6.0, 8.0), Vector::new(-8.0, 18.0, 32.0));
+        assert_eq!(t.inverted() * Vector::new(-4.0, 6.0, 8.0), Vector::new(-2.0, 2.0, 2.0));
+
+        let reflect = Transform::scaling(-1.0, 1.0, 1.0);
+        assert_eq!(reflect * Point::new(2.0, 3.0, 4.0), Point::new(-2.0, 3.0, 4.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn scaling_by_zero_panics() {
+        Transform::scaling(1.0, 0.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "translation must be finite")]
+    fn non_finite_translation_panics() {
+        Transform::translation(0.0, f64::NAN, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "scaling must be finite")]
+    fn non_finite_scaling_panics() {
+        Transform::scaling(1.0, 1.0, f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "scaling must be finite")]
+    fn scaling_with_overflowing_inverse_panics() {
+        Transform::scaling(1.0e-310, 1.0, 1.0);
+    }
+
+    #[test]
+    fn rotations_around_the_axes() {
+        let h = SQRT_2 / 2.0;
+
+        let p = Point::new(0.0, 1.0, 0.0);
+        assert_point_eq(Transform::rotation_x(EIGHTH) * p, Point::new(0.0, h, h));
+        assert_point_eq(Transform::rotation_x(QUARTER) * p, Point::new(0.0, 0.0, 1.0));
+        assert_point_eq(Transform::rotation_x(EIGHTH).inverted() * p, Point::new(0.0, h, -h));
+
+        let p = Point::new(0.0, 0.0, 1.0);
+        assert_point_eq(Transform::rotation_y(EIGHTH) * p, Point::new(h, 0.0, h));
+        assert_point_eq(Transform::rotation_y(QUARTER) * p, Point::new(1.0, 0.0, 0.0));
+
+        let p = Point::new(0.0, 1.0, 0.0);
+        assert_point_eq(Transform::rotation_z(EIGHTH) * p, Point::new(-h, h, 0.0));
+        assert_point_eq(Transform::rotation_z(QUARTER) * p, Point::new(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn rotation_around_an_unnormalized_axis() {
+        let axis = Vector::new(0.0, 0.0, 5.0);
+        let p = Point::new(0.0, 1.0, 0.0);
+
+        assert_point_eq(Transform::rotation(&axis, QUARTER) * p, Transform::rotation_z(QUARTER) * p);
+    }
+
+    #[test]
+    fn rotations_accept_degrees() {
+        let p = Point::new(0.0, 1.0, 0.0);
+
+        assert_point_eq(Transform::rotation_x(Angle::degrees(90.0)) * p, Transform::rotation_x(QUARTER) * p);
+    }
+
+    #[test]
+    fn shearing_moves_components_in_proportion() {
+        let p = Point::new(2.0, 3.0, 4.0);
+
+        assert_eq!(Transform::shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0) * p, Point::new(5.0, 3.0, 4.0));
+        assert_eq!(Transform::shearing(0.0, 1.0, 0.0, 0.0, 0.0, 0.0) * p, Point::new(6.0, 3.0, 4.0));
+        assert_eq!(Transform::shearing(0.0, 0.0, 1.0, 0.0, 0.0, 0.0) * p, Point::new(2.0, 5.0, 4.0));
+        assert_eq!(Transform::shearing(0.0, 0.0, 0.0, 1.0, 0.0, 0.0) * p, Point::new(2.0, 7.0, 4.0));
+        assert_eq!(Transform::shearing(0.0, 0.0, 0.0, 0.0, 1.0, 0.0) * p, Point::new(2.0, 3.0, 6.0));
+        assert_eq!(Transform::shearing(0.0, 0.0, 0.0, 0.0, 0.0, 1.0) * p, Point::new(2.0, 3.0, 7.0));
+    }
+
+    #[test]
+    fn chained_transformations_apply_in_order() {
+        let p = Point::new(1.0, 0.0, 1.0);
+        let a = Transform::rotation_x(QUARTER);
+        let b = Transform::scaling(5.0, 5.0, 5.0);
+        let c = Transform::translation(10.0, 5.0, 7.0);
+
+        let t = a.then(&b).then(&c);
+        assert_point_eq(t * p, Point::new(15.0, 0.0, 7.0));
+        assert_point_eq(c * b * a * p, Point::new(15.0, 0.0, 7.0));
+        assert_point_eq(t.inverted() * Point::new(15.0, 0.0, 7.0), p);
+    }
+
+    #[test]
+    fn singular_matrices_are_rejected() {
+        assert!(Transform::new(Matrix4::zero()).is_none());
+        assert!(Transform::new(Matrix4::ident()).is_some());
+    }
+
+    #[test]
+    fn non_finite_matrices_are_rejected() {
+        let mut m = Matrix4::ident();
+        m.data[0][0] = f64::NAN;
+        assert!(Transform::new(m).is_none());
+
+        let mut m = Matrix4::ident();
+        m.data[1][3] = f64::INFINITY;
+        assert!(Transform::new(m).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rotation_around_zero_axis_panics() {
+        Transform::rotation(&Vector::new(0.0, 0.0, 0.0), QUARTER);
+    }
+}
@@ -1,30 +1,48 @@
-use auto_ops::*;
+use std::ops::{Add, Div, Mul, Neg, Sub};
 
 use super::Point;
 
-// Vector
-coordinate_struct!(Vector, x, y, z);
+coordinate_struct!(
+    /// Direction and magnitude in space
+    ///
+    /// ```
+    /// use ray_tracer_challenge::Vector;
+    ///
+    /// let x = Vector::new(1.0, 0.0, 0.0);
+    /// let y = Vector::new(0.0, 1.0, 0.0);
+    ///
+    /// assert_eq!(x.cross(&y), Vector::new(0.0, 0.0, 1.0));
+    /// assert_eq!(x.dot(&y), 0.0);
+    /// assert_eq!((x * 3.0).normalize(), x);
+    /// ```
+    Vector, x, y, z
+);
 
 // From point to vector
 coordinate_struct_convert!(Vector, Point, x, y, z);
 
 impl Vector {
+    #[inline]
     pub fn length_squared(&self) -> f64 {
-        self.x * self.x + self.y * self.y + self.z * self.z
+        self.dot(self)
     }
 
+    #[inline]
     pub fn length(&self) -> f64 {
         self.length_squared().sqrt()
     }
 
+    #[inline]
     pub fn normalize(&self) -> Vector {
         self / self.length()
     }
 
+    #[inline]
     pub fn dot(&self, other: &Self) -> f64 {
         self.x * other.x + self.y * other.y + self.z * other.z
     }
 
+    #[inline]
     pub fn cross(&self, other: &Self) -> Self {
         let x = self.y * other.z - self.z * other.y;
         let y = self.z * other.x - self.x * other.z;
@@ -33,6 +51,16 @@ impl Vector {
         Self::new(x, y, z)
     }
 
+    /// Add a scalar to every component
+    ///
+    /// Deliberately not an `Add<f64>` implementation, so that mixing vectors and scalars by
+    /// accident fails to compile.
+    #[inline]
+    pub fn broadcast_add(&self, value: f64) -> Self {
+        self + Self::new(value, value, value)
+    }
+
+    #[inline]
     pub fn hadamard(&self, other: &Self) -> Self {
         let x = self.x * other.x;
         let y = self.y * other.y;
@@ -43,52 +71,80 @@ impl Vector {
 }
 
 // Inverse
-impl_op_ex!(-|v: &Vector| -> Vector {
-    let x = -v.x;
-    let y = -v.y;
-    let z = -v.z;
+impl Neg for &Vector {
+    type Output = Vector;
+
+    #[inline]
+    fn neg(self) -> Vector {
+        let x = -self.x;
+        let y = -self.y;
+        let z = -self.z;
+
+        Vector::new(x, y, z)
+    }
+}
 
-    Vector::new(x, y, z)
-});
+forward_ref_unop!(impl Neg, neg for Vector => Vector);
 
 // Vector and vector addition
-impl_op_ex!(+|lhs: &Vector, rhs: &Vector| -> Vector {
-    let x = lhs.x + rhs.x;
-    let y = lhs.y + rhs.y;
-    let z = lhs.z + rhs.z;
+impl Add for &Vector {
+    type Output = Vector;
+
+    #[inline]
+    fn add(self, rhs: &Vector) -> Vector {
+        let x = self.x + rhs.x;
+        let y = self.y + rhs.y;
+        let z = self.z + rhs.z;
+
+        Vector::new(x, y, z)
+    }
+}
 
-    Vector::new(x, y, z)
-});
+forward_ref_binop!(impl Add, add for Vector, Vector => Vector);
 
 // Vector and vector subtraction
-impl_op_ex!(-|lhs: &Vector, rhs: &Vector| -> Vector {
-    let rhs = -rhs;
+impl Sub for &Vector {
+    type Output = Vector;
 
-    lhs + rhs
-});
+    #[inline]
+    fn sub(self, rhs: &Vector) -> Vector {
+        let rhs = -rhs;
 
-// Vector and scalar addition
-impl_op_ex_commutative!(+|lhs: &Vector, rhs: &f64| -> Vector {
-    let rhs = Vector::new(*rhs, *rhs, *rhs);
+        self + rhs
+    }
+}
 
-    lhs + rhs
-});
+forward_ref_binop!(impl Sub, sub for Vector, Vector => Vector);
 
 // Vector and scalar multiplication
-impl_op_ex_commutative!(*|lhs: &Vector, rhs: &f64| -> Vector {
-    let x = lhs.x * rhs;
-    let y = lhs.y * rhs;
-    let z = lhs.z * rhs;
+impl Mul<&f64> for &Vector {
+    type Output = Vector;
+
+    #[inline]
+    fn mul(self, rhs: &f64) -> Vector {
+        let x = self.x * rhs;
+        let y = self.y * rhs;
+        let z = self.z * rhs;
 
-    Vector::new(x, y, z)
-});
+        Vector::new(x, y, z)
+    }
+}
+
+forward_ref_binop!(impl Mul, mul for Vector, f64 => Vector);
+commutative_binop!(impl Mul, mul for Vector, f64 => Vector);
 
 // Vector and scalar division
-impl_op_ex_commutative!(/|lhs: &Vector, rhs: &f64| -> Vector {
-    let x = lhs.x / rhs;
-    let y = lhs.y / rhs;
-    let z = lhs.z / rhs;
+impl Div<&f64> for &Vector {
+    type Output = Vector;
+
+    #[inline]
+    fn div(self, rhs: &f64) -> Vector {
+        let x = self.x / rhs;
+        let y = self.y / rhs;
+        let z = self.z / rhs;
 
-    Vector::new(x, y, z)
-});
+        Vector::new(x, y, z)
+    }
+}
 
+forward_ref_binop!(impl Div, div for Vector, f64 => Vector);
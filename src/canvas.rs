@@ -2,18 +2,73 @@ use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
-use super::Color;
+use super::font::{self, GLYPH_HEIGHT, GLYPH_WIDTH};
+use super::{png, Color, Rgb8, BLACK, WHITE};
 
 #[derive(Debug)]
 pub enum CanvasError {
     InvalidIndex,
+    SizeMismatch,
+}
+
+/// How [`Canvas::composite`] combines a source canvas with the destination
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum BlendMode {
+    /// Source drawn on top, covering the destination by its alpha
+    Over,
+    /// Source added to the destination
+    Add,
+    /// Source and destination multiplied (darkens)
+    Multiply,
+    /// Inverted source and destination multiplied (lightens)
+    Screen,
+}
+
+impl BlendMode {
+    // Blended color of backdrop `b` and source `s`, before alpha compositing
+    fn blend(self, b: &Color, s: &Color) -> Color {
+        match self {
+            BlendMode::Over => *s,
+            BlendMode::Add => b + s,
+            BlendMode::Multiply => b * s,
+            BlendMode::Screen => {
+                let one = Color::one();
+                one - (one - b) * (one - s)
+            }
+        }
+    }
+}
+
+/// Dithering strategy used when quantizing a canvas to 8-bit color
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub enum Dither {
+    /// Plain per-pixel quantization
+    #[default]
+    None,
+    /// Floyd–Steinberg error diffusion, removes banding in smooth gradients
+    FloydSteinberg,
 }
 
 /// Rectangular grid of pixels
+///
+/// ```
+/// use ray_tracer_challenge::{Canvas, Color, Dither, Rgb8, BLACK};
+///
+/// let mut canvas = Canvas::new(10, 10);
+/// *canvas.pixel_mut(2, 3).unwrap() = Color::new(1.0, 0.0, 0.0);
+///
+/// assert_eq!(*canvas.pixel(0, 0).unwrap(), BLACK);
+/// assert!(canvas.pixel(10, 10).is_err());
+///
+/// let quantized = canvas.to_rgb8(Dither::None);
+/// assert_eq!(quantized[2 + 3 * 10], Rgb8::new(255, 0, 0));
+/// ```
+#[derive(Clone)]
 pub struct Canvas {
     width: usize,
     height: usize,
     pixels: Vec<Color>,
+    alpha: Option<Vec<f64>>,
 }
 
 impl Canvas {
@@ -26,6 +81,7 @@ impl Canvas {
             width,
             height,
             pixels: vec![color; width * height],
+            alpha: None,
         }
     }
 
@@ -67,11 +123,269 @@ impl Canvas {
         Ok(&mut self.pixels[i])
     }
 
+    pub fn has_alpha(&self) -> bool {
+        self.alpha.is_some()
+    }
+
+    /// Per-pixel alpha, if the canvas has an alpha channel
+    pub fn alphas(&self) -> Option<&Vec<f64>> {
+        self.alpha.as_ref()
+    }
+
+    /// Per-pixel alpha, adding a fully opaque alpha channel if there is none
+    pub fn alphas_mut(&mut self) -> &mut Vec<f64> {
+        let n = self.pixels.len();
+
+        self.alpha.get_or_insert_with(|| vec![1.0; n])
+    }
+
+    /// Alpha of a pixel (fully opaque without an alpha channel)
+    pub fn alpha(&self, x: usize, y: usize) -> Result<f64, CanvasError> {
+        let i = self.pixel_index(x, y)?;
+
+        Ok(self.alpha.as_ref().map_or(1.0, |a| a[i]))
+    }
+
+    /// Alpha of a pixel, adding a fully opaque alpha channel if there is none
+    pub fn alpha_mut(&mut self, x: usize, y: usize) -> Result<&mut f64, CanvasError> {
+        let i = self.pixel_index(x, y)?;
+
+        Ok(&mut self.alphas_mut()[i])
+    }
+
+    /// Composite `source` on top of this canvas
+    ///
+    /// Both canvases are treated as straight (non-premultiplied) alpha, with a missing alpha
+    /// channel meaning fully opaque. The result only has an alpha channel if either input had one.
+    pub fn composite(&self, source: &Canvas, mode: BlendMode) -> Result<Canvas, CanvasError> {
+        if self.width != source.width || self.height != source.height {
+            return Err(CanvasError::SizeMismatch);
+        }
+
+        let mut res = self.clone();
+        let with_alpha = self.has_alpha() || source.has_alpha();
+        let mut alpha = Vec::with_capacity(if with_alpha { self.pixels.len() } else { 0 });
+
+        for (i, p) in res.pixels.iter_mut().enumerate() {
+            let alpha_b = self.alpha.as_ref().map_or(1.0, |a| a[i]);
+            let alpha_s = source.alpha.as_ref().map_or(1.0, |a| a[i]);
+            let cb = self.pixels[i];
+            let cs = source.pixels[i];
+
+            // Source color mixed with the blend result by how much backdrop is underneath
+            let cs = cs * (1.0 - alpha_b) + mode.blend(&cb, &cs) * alpha_b;
+            let alpha_o = alpha_s + alpha_b * (1.0 - alpha_s);
+
+            *p = if alpha_o > 0.0 {
+                (cs * alpha_s + cb * (alpha_b * (1.0 - alpha_s))) * (1.0 / alpha_o)
+            } else {
+                Color::zero()
+            };
+
+            if with_alpha {
+                alpha.push(alpha_o);
+            }
+        }
+        res.alpha = with_alpha.then_some(alpha);
+
+        Ok(res)
+    }
+
+    /// Draw `text` with its top-left corner at (`x`, `y`) using the embedded 5x7 pixel font
+    ///
+    /// Characters advance by six pixels and `\n` starts a new line eight pixels down. Characters
+    /// outside printable ASCII are drawn as `?`, and anything outside the canvas is clipped.
+    pub fn draw_text(&mut self, x: usize, y: usize, text: &str, color: Color) {
+        let (mut cx, mut cy) = (x, y);
+
+        for c in text.chars() {
+            if c == '\n' {
+                cx = x;
+                cy += GLYPH_HEIGHT + 1;
+                continue;
+            }
+
+            for (i, column) in font::glyph(c).iter().enumerate() {
+                for j in (0..GLYPH_HEIGHT).filter(|j| column & (1 << j) != 0) {
+                    let (px, py) = (cx + i, cy + j);
+                    if px < self.width && py < self.height {
+                        self.pixels[px + py * self.width] = color;
+                    }
+                }
+            }
+
+            cx += GLYPH_WIDTH + 1;
+        }
+    }
+
+    /// Add `other` scaled by `weight` onto this canvas
+    ///
+    /// Used to recombine separately rendered passes (direct light, reflections, per-light...)
+    /// with new weights, without re-rendering.
+    ///
+    /// If `other` has an alpha channel, it is accumulated the same way, with a missing alpha
+    /// channel on this canvas counting as fully opaque. Start from a canvas with a zeroed alpha
+    /// channel to accumulate coverage from nothing.
+    pub fn accumulate(&mut self, other: &Canvas, weight: f64) -> Result<(), CanvasError> {
+        if self.width != other.width || self.height != other.height {
+            return Err(CanvasError::SizeMismatch);
+        }
+
+        for (p, o) in self.pixels.iter_mut().zip(other.pixels.iter()) {
+            *p = o.scaled_add(weight, p);
+        }
+
+        if let Some(other_alpha) = other.alpha.as_ref() {
+            for (a, o) in self.alphas_mut().iter_mut().zip(other_alpha.iter()) {
+                *a += o * weight;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Combine a stereo pair into a red/cyan anaglyph (red from the left eye, green and blue from
+    /// the right)
+    ///
+    /// A pixel is as opaque as the more opaque of the two eyes, with a missing alpha channel
+    /// meaning fully opaque. The result only has an alpha channel if either input had one.
+    pub fn anaglyph(left: &Canvas, right: &Canvas) -> Result<Canvas, CanvasError> {
+        if left.width != right.width || left.height != right.height {
+            return Err(CanvasError::SizeMismatch);
+        }
+
+        let pixels = left
+            .pixels
+            .iter()
+            .zip(right.pixels.iter())
+            .map(|(l, r)| Color::new(l.r, r.g, r.b))
+            .collect();
+
+        let alpha = (left.has_alpha() || right.has_alpha()).then(|| {
+            (0..left.pixels.len())
+                .map(|i| {
+                    let alpha_l = left.alpha.as_ref().map_or(1.0, |a| a[i]);
+                    let alpha_r = right.alpha.as_ref().map_or(1.0, |a| a[i]);
+
+                    alpha_l.max(alpha_r)
+                })
+                .collect()
+        });
+
+        Ok(Self {
+            width: left.width,
+            height: left.height,
+            pixels,
+            alpha,
+        })
+    }
+
+    /// Place a stereo pair next to each other, left eye on the left
+    ///
+    /// The result only has an alpha channel if either input had one, with the side missing one
+    /// being fully opaque.
+    pub fn side_by_side(left: &Canvas, right: &Canvas) -> Result<Canvas, CanvasError> {
+        if left.height != right.height {
+            return Err(CanvasError::SizeMismatch);
+        }
+
+        let mut res = Self::new(left.width + right.width, left.height);
+        for y in 0..left.height {
+            let row = &mut res.pixels[y * res.width..(y + 1) * res.width];
+            row[..left.width].copy_from_slice(&left.pixels[y * left.width..(y + 1) * left.width]);
+            row[left.width..].copy_from_slice(&right.pixels[y * right.width..(y + 1) * right.width]);
+        }
+
+        if left.has_alpha() || right.has_alpha() {
+            let (width, left_width) = (res.width, left.width);
+            let alpha = res.alphas_mut();
+            for y in 0..left.height {
+                let row = &mut alpha[y * width..(y + 1) * width];
+                if let Some(a) = left.alpha.as_ref() {
+                    row[..left_width].copy_from_slice(&a[y * left_width..(y + 1) * left_width]);
+                }
+                if let Some(a) = right.alpha.as_ref() {
+                    row[left_width..].copy_from_slice(&a[y * right.width..(y + 1) * right.width]);
+                }
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Quantize the canvas to 8-bit color
+    pub fn to_rgb8(&self, dither: Dither) -> Vec<Rgb8> {
+        match dither {
+            Dither::None => self.pixels.iter().map(|&p| Rgb8::from(p)).collect(),
+            Dither::FloydSteinberg => self.floyd_steinberg(),
+        }
+    }
+
+    fn floyd_steinberg(&self) -> Vec<Rgb8> {
+        let mut pixels = self.pixels.clone();
+        let mut res = Vec::with_capacity(pixels.len());
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let i = x + y * self.width;
+                // Clamped first, so that out of range HDR values don't smear into their neighbours
+                let p = pixels[i].max(&BLACK).min(&WHITE);
+                let q = Rgb8::from(p);
+                let error = p - Color::from(q);
+
+                let mut diffuse = |dx: isize, dy: usize, weight: f64| {
+                    let nx = x as isize + dx;
+                    let ny = y + dy;
+                    if nx >= 0 && (nx as usize) < self.width && ny < self.height {
+                        let j = nx as usize + ny * self.width;
+                        pixels[j] = error.scaled_add(weight, &pixels[j]);
+                    }
+                };
+
+                diffuse(1, 0, 7.0 / 16.0);
+                diffuse(-1, 1, 3.0 / 16.0);
+                diffuse(0, 1, 5.0 / 16.0);
+                diffuse(1, 1, 1.0 / 16.0);
+
+                res.push(q);
+            }
+        }
+
+        res
+    }
+
+    /// Write the canvas as a PNG, including the alpha channel if there is one
+    pub fn write_png<P: AsRef<Path>>(&self, path: P, dither: Dither) -> std::io::Result<()> {
+        let mut f = File::create(path)?;
+
+        let colors = self.to_rgb8(dither);
+        let (channels, data) = match self.alpha.as_ref() {
+            Some(alpha) => {
+                let data = colors
+                    .iter()
+                    .zip(alpha.iter())
+                    .flat_map(|(c, &a)| {
+                        let a = ((255.0 * a) as u64).clamp(0, 255) as u8;
+                        vec![c.r, c.g, c.b, a]
+                    })
+                    .collect::<Vec<u8>>();
+                (4, data)
+            }
+            None => (3, colors.iter().flat_map(|c| vec![c.r, c.g, c.b]).collect()),
+        };
+
+        png::write_png(&mut f, self.width, self.height, channels, &data)
+    }
+
     pub fn write_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        self.write_file_dithered(path, Dither::None)
+    }
+
+    pub fn write_file_dithered<P: AsRef<Path>>(&self, path: P, dither: Dither) -> std::io::Result<()> {
         let mut f = File::create(path)?;
 
         writeln!(f, "P3\n{} {}\n255", self.width, self.height)?;
-        for p in self.pixels.iter() {
+        for p in self.to_rgb8(dither).iter() {
             writeln!(f, "{}", p)?;
         }
 
@@ -81,7 +395,7 @@ impl Canvas {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Canvas, BLACK, WHITE};
+    use crate::{Canvas, Color, Dither, Rgb8, BLACK, WHITE};
 
     #[test]
     fn new_canvas_is_all_black() {
@@ -103,4 +417,175 @@ mod tests {
         assert!(canvas.pixels()[1..].iter().all(|&p| p == BLACK));
         assert!(*canvas.pixel(0, 0).unwrap() == WHITE);
     }
+
+    #[test]
+    fn alpha_channel_is_optional() {
+        let mut canvas = Canvas::new(2, 2);
+
+        assert!(!canvas.has_alpha());
+        assert_eq!(canvas.alpha(1, 1).unwrap(), 1.0);
+
+        *canvas.alpha_mut(1, 1).unwrap() = 0.25;
+        assert!(canvas.has_alpha());
+        assert_eq!(canvas.alpha(1, 1).unwrap(), 0.25);
+        assert_eq!(canvas.alpha(0, 0).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn compositing_with_blend_modes() {
+        use crate::BlendMode;
+
+        let dst = Canvas::with_color(1, 1, Color::new(0.5, 0.5, 0.5));
+        let src = Canvas::with_color(1, 1, Color::new(0.5, 1.0, 0.0));
+        let composite = |mode| *dst.composite(&src, mode).unwrap().pixel(0, 0).unwrap();
+
+        assert_eq!(composite(BlendMode::Over), Color::new(0.5, 1.0, 0.0));
+        assert_eq!(composite(BlendMode::Add), Color::new(1.0, 1.5, 0.5));
+        assert_eq!(composite(BlendMode::Multiply), Color::new(0.25, 0.5, 0.0));
+        assert_eq!(composite(BlendMode::Screen), Color::new(0.75, 1.0, 0.5));
+        assert!(!dst.composite(&src, BlendMode::Over).unwrap().has_alpha());
+    }
+
+    #[test]
+    fn compositing_respects_alpha() {
+        use crate::BlendMode;
+
+        let dst = Canvas::with_color(1, 1, BLACK);
+        let mut src = Canvas::with_color(1, 1, WHITE);
+        *src.alpha_mut(0, 0).unwrap() = 0.25;
+
+        let res = dst.composite(&src, BlendMode::Over).unwrap();
+        assert_eq!(*res.pixel(0, 0).unwrap(), Color::gray(0.25));
+        assert_eq!(res.alpha(0, 0).unwrap(), 1.0);
+
+        // Over a fully transparent backdrop the source is kept as is
+        let mut dst = Canvas::with_color(1, 1, BLACK);
+        *dst.alpha_mut(0, 0).unwrap() = 0.0;
+        let res = dst.composite(&src, BlendMode::Multiply).unwrap();
+        assert_eq!(*res.pixel(0, 0).unwrap(), WHITE);
+        assert_eq!(res.alpha(0, 0).unwrap(), 0.25);
+
+        assert!(dst.composite(&Canvas::new(2, 1), BlendMode::Over).is_err());
+    }
+
+    #[test]
+    fn drawing_text() {
+        let mut canvas = Canvas::new(12, 16);
+        canvas.draw_text(0, 0, "HI\n!", WHITE);
+
+        let lit = |x, y| *canvas.pixel(x, y).unwrap() == WHITE;
+
+        // 'H': two full-height stems joined in the middle
+        assert!((0..7).all(|y| lit(0, y) && lit(4, y)));
+        assert!(lit(2, 3) && !lit(2, 2));
+        // 'I' starts after a one pixel gap, its stem is the third column
+        assert!(!lit(5, 0) && (0..7).all(|y| lit(8, y)));
+        // '!' on the next line, with a gap above the dot
+        assert!(lit(2, 8) && !lit(2, 13) && lit(2, 14));
+        assert_eq!(canvas.pixels().iter().filter(|&&p| p == WHITE).count(), 17 + 11 + 6);
+
+        // Clipped at the edges rather than panicking
+        canvas.draw_text(10, 14, "W\u{263a}", WHITE);
+    }
+
+    #[test]
+    fn recombining_weighted_passes() {
+        let direct = Canvas::with_color(2, 2, Color::new(0.5, 0.0, 0.0));
+        let reflect = Canvas::with_color(2, 2, Color::new(0.0, 0.5, 0.0));
+
+        let mut res = Canvas::new(2, 2);
+        res.accumulate(&direct, 2.0).unwrap();
+        res.accumulate(&reflect, 0.5).unwrap();
+
+        assert!(res.pixels().iter().all(|&p| p == Color::new(1.0, 0.25, 0.0)));
+        assert!(res.accumulate(&Canvas::new(1, 2), 1.0).is_err());
+    }
+
+    #[test]
+    fn accumulating_carries_alpha() {
+        let mut pass = Canvas::with_color(2, 1, WHITE);
+        *pass.alpha_mut(0, 0).unwrap() = 0.5;
+
+        let mut res = Canvas::new(2, 1);
+        res.accumulate(&pass, 0.5).unwrap();
+        assert_eq!(res.alpha(0, 0).unwrap(), 1.25);
+
+        let mut res = Canvas::new(2, 1);
+        res.alphas_mut().iter_mut().for_each(|a| *a = 0.0);
+        res.accumulate(&pass, 0.5).unwrap();
+        res.accumulate(&pass, 0.5).unwrap();
+        assert_eq!(res.alphas().unwrap(), &vec![0.5, 1.0]);
+
+        let mut res = Canvas::new(2, 1);
+        res.accumulate(&Canvas::new(2, 1), 1.0).unwrap();
+        assert!(!res.has_alpha());
+    }
+
+    #[test]
+    fn stereo_pair_compositing() {
+        let left = Canvas::with_color(2, 1, Color::new(0.1, 0.2, 0.3));
+        let right = Canvas::with_color(3, 1, Color::new(0.4, 0.5, 0.6));
+
+        assert!(Canvas::anaglyph(&left, &right).is_err());
+        let anaglyph = Canvas::anaglyph(&left, &left).unwrap();
+        assert_eq!(anaglyph.pixels(), left.pixels());
+
+        let pair = Canvas::side_by_side(&left, &right).unwrap();
+        assert_eq!(pair.width(), 5);
+        assert_eq!(*pair.pixel(1, 0).unwrap(), Color::new(0.1, 0.2, 0.3));
+        assert_eq!(*pair.pixel(2, 0).unwrap(), Color::new(0.4, 0.5, 0.6));
+
+        let right = Canvas::with_color(2, 1, Color::new(0.4, 0.5, 0.6));
+        let anaglyph = Canvas::anaglyph(&left, &right).unwrap();
+        assert_eq!(*anaglyph.pixel(0, 0).unwrap(), Color::new(0.1, 0.5, 0.6));
+    }
+
+    #[test]
+    fn stereo_pair_compositing_carries_alpha() {
+        let mut left = Canvas::new(2, 1);
+        *left.alpha_mut(0, 0).unwrap() = 0.25;
+        let mut right = Canvas::new(2, 1);
+        *right.alpha_mut(0, 0).unwrap() = 0.5;
+        *right.alpha_mut(1, 0).unwrap() = 0.0;
+
+        let anaglyph = Canvas::anaglyph(&left, &right).unwrap();
+        assert_eq!(anaglyph.alphas().unwrap(), &vec![0.5, 1.0]);
+
+        let pair = Canvas::side_by_side(&left, &right).unwrap();
+        assert_eq!(pair.alphas().unwrap(), &vec![0.25, 1.0, 0.5, 0.0]);
+
+        let pair = Canvas::side_by_side(&Canvas::new(1, 1), &right).unwrap();
+        assert_eq!(pair.alphas().unwrap(), &vec![1.0, 0.5, 0.0]);
+
+        let plain = Canvas::new(2, 1);
+        assert!(!Canvas::anaglyph(&plain, &plain).unwrap().has_alpha());
+        assert!(!Canvas::side_by_side(&plain, &plain).unwrap().has_alpha());
+    }
+
+    #[test]
+    fn floyd_steinberg_preserves_average_level() {
+        // Halfway between two 8-bit levels
+        let level = 100.5 / 255.0;
+        let canvas = Canvas::with_color(16, 16, Color::gray(level));
+
+        let plain = canvas.to_rgb8(Dither::None);
+        assert!(plain.iter().all(|&p| p == Rgb8::new(100, 100, 100)));
+
+        let dithered = canvas.to_rgb8(Dither::FloydSteinberg);
+        let mean = dithered.iter().map(|p| p.r as f64).sum::<f64>() / dithered.len() as f64;
+        assert!((mean - 100.5).abs() < 0.1);
+        assert!(dithered.iter().all(|p| p.r == 100 || p.r == 101));
+    }
+
+    #[test]
+    fn floyd_steinberg_ignores_out_of_range_error() {
+        let mut canvas = Canvas::new(4, 2);
+        *canvas.pixel_mut(1, 0).unwrap() = Color::gray(5.0);
+        for x in 0..4 {
+            *canvas.pixel_mut(x, 1).unwrap() = WHITE;
+        }
+        *canvas.pixel_mut(2, 1).unwrap() = Color::gray(-3.0);
+
+        assert_eq!(canvas.to_rgb8(Dither::FloydSteinberg), canvas.to_rgb8(Dither::None));
+    }
 }
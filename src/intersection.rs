@@ -0,0 +1,159 @@
+use std::ops::Index;
+use std::ptr;
+use std::slice::Iter;
+
+use super::Shape;
+
+/// Ray-object intersection at distance `t` along the ray
+///
+/// Shapes that interpolate across their surface (like [`SmoothTriangle`](crate::SmoothTriangle))
+/// also record where on the surface they were hit, as barycentric `u` and `v`.
+#[derive(Copy, Clone, Debug)]
+pub struct Intersection<'a> {
+    pub t: f64,
+    pub object: &'a dyn Shape,
+    pub u: f64,
+    pub v: f64,
+}
+
+impl<'a> Intersection<'a> {
+    pub fn new(t: f64, object: &'a dyn Shape) -> Self {
+        Self::with_uv(t, object, 0.0, 0.0)
+    }
+
+    /// Intersection at the surface coordinates `u` and `v`
+    pub fn with_uv(t: f64, object: &'a dyn Shape, u: f64, v: f64) -> Self {
+        Self { t, object, u, v }
+    }
+}
+
+// Intersections are equal when at the same distance on the same object (by identity)
+impl PartialEq for Intersection<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.t == other.t && ptr::addr_eq(self.object, other.object)
+    }
+}
+
+/// Collection of intersections, kept sorted by distance
+///
+/// Can be reused between rays (see [`clear`](Intersections::clear)) to avoid allocating a new
+/// buffer for every ray.
+#[derive(Clone, Default, Debug)]
+pub struct Intersections<'a> {
+    data: Vec<Intersection<'a>>,
+}
+
+impl<'a> Intersections<'a> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Insert an intersection, keeping the collection sorted
+    pub fn push(&mut self, i: Intersection<'a>) {
+        let at = self.data.partition_point(|x| x.t <= i.t);
+        self.data.insert(at, i);
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, Intersection<'a>> {
+        self.data.iter()
+    }
+
+    /// The visible intersection, i.e. the nearest one not behind the ray origin
+    pub fn hit(&self) -> Option<&Intersection<'a>> {
+        self.data.iter().find(|i| i.t >= 0.0)
+    }
+}
+
+impl<'a> Index<usize> for Intersections<'a> {
+    type Output = Intersection<'a>;
+
+    fn index(&self, i: usize) -> &Intersection<'a> {
+        &self.data[i]
+    }
+}
+
+impl<'a> From<Vec<Intersection<'a>>> for Intersections<'a> {
+    fn from(data: Vec<Intersection<'a>>) -> Self {
+        let mut res = Self { data };
+        res.data.sort_by(|a, b| a.t.total_cmp(&b.t));
+        res
+    }
+}
+
+impl<'a, 'b> IntoIterator for &'b Intersections<'a> {
+    type Item = &'b Intersection<'a>;
+    type IntoIter = Iter<'b, Intersection<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Intersection, Intersections, Sphere};
+
+    #[test]
+    fn intersections_are_kept_sorted() {
+        let s = Sphere::new();
+        let mut xs = Intersections::new();
+        xs.push(Intersection::new(2.0, &s));
+        xs.push(Intersection::new(-1.0, &s));
+        xs.push(Intersection::new(1.0, &s));
+
+        let ts: Vec<f64> = xs.iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![-1.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn hit_when_all_intersections_have_positive_t() {
+        let s = Sphere::new();
+        let i1 = Intersection::new(1.0, &s);
+        let i2 = Intersection::new(2.0, &s);
+        let xs = Intersections::from(vec![i2, i1]);
+
+        assert_eq!(xs.hit(), Some(&i1));
+    }
+
+    #[test]
+    fn hit_when_some_intersections_have_negative_t() {
+        let s = Sphere::new();
+        let i1 = Intersection::new(-1.0, &s);
+        let i2 = Intersection::new(1.0, &s);
+        let xs = Intersections::from(vec![i2, i1]);
+
+        assert_eq!(xs.hit(), Some(&i2));
+    }
+
+    #[test]
+    fn hit_when_all_intersections_have_negative_t() {
+        let s = Sphere::new();
+        let xs = Intersections::from(vec![Intersection::new(-2.0, &s), Intersection::new(-1.0, &s)]);
+
+        assert_eq!(xs.hit(), None);
+    }
+
+    #[test]
+    fn hit_is_always_the_lowest_nonnegative_intersection() {
+        let s = Sphere::new();
+        let i1 = Intersection::new(5.0, &s);
+        let i2 = Intersection::new(7.0, &s);
+        let i3 = Intersection::new(-3.0, &s);
+        let i4 = Intersection::new(2.0, &s);
+        let xs = Intersections::from(vec![i1, i2, i3, i4]);
+
+        assert_eq!(xs.hit(), Some(&i4));
+    }
+}
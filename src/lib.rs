@@ -1,9 +1,47 @@
-#![feature(bool_to_option, clamp)]
+//! A ray tracer following [The Ray Tracer Challenge](http://raytracerchallenge.com/)
+//!
+//! ```
+//! use ray_tracer_challenge::*;
+//!
+//! // Invert a matrix
+//! let m = Matrix2::new([[2.0, 0.0], [0.0, 4.0]]);
+//! let i = m * m.inverse().unwrap();
+//! assert_eq!(i, Matrix2::ident());
+//!
+//! // Post-process a canvas, darkening the corners but not the center
+//! let canvas = Canvas::with_color(5, 5, WHITE);
+//! let canvas = PostProcess::new()
+//!     .with(Vignette { strength: 0.5, radius: 0.5 })
+//!     .apply(&canvas);
+//!
+//! assert_eq!(*canvas.pixel(0, 0).unwrap(), Color::gray(0.5));
+//! assert_eq!(*canvas.pixel(2, 2).unwrap(), WHITE);
+//! ```
+
 // #![allow(dead_code)]
 // #![allow(unused_imports)]
 
 mod base_types;
 mod canvas;
+mod font;
+mod intersection;
+mod material;
+mod obj;
+pub mod palette;
+mod png;
+mod point_cloud;
+mod post;
+mod quadratic;
+mod ray;
+mod shapes;
 
 pub use base_types::*;
 pub use canvas::*;
+pub use intersection::*;
+pub use material::*;
+pub use obj::*;
+pub use point_cloud::*;
+pub use post::*;
+pub use quadratic::*;
+pub use ray::*;
+pub use shapes::*;
@@ -0,0 +1,23 @@
+use super::{Color, WHITE};
+
+/// Surface appearance of a shape (Phong reflection model parameters)
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Material {
+    pub color: Color,
+    pub ambient: f64,
+    pub diffuse: f64,
+    pub specular: f64,
+    pub shininess: f64,
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Self {
+            color: WHITE,
+            ambient: 0.1,
+            diffuse: 0.9,
+            specular: 0.9,
+            shininess: 200.0,
+        }
+    }
+}
@@ -0,0 +1,385 @@
+//! Wavefront OBJ meshes
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use super::{Group, Point, SmoothTriangle, Triangle, Vector};
+
+#[derive(Debug)]
+pub enum ObjError {
+    Io(io::Error),
+    /// Malformed statement, with its line number (counting from 1)
+    InvalidLine(usize),
+    /// Face referring to a vertex or normal that doesn't exist, with its line number
+    InvalidIndex(usize),
+}
+
+impl From<io::Error> for ObjError {
+    fn from(e: io::Error) -> Self {
+        ObjError::Io(e)
+    }
+}
+
+/// Triangle of an OBJ face, as indices into [`ObjParser::vertices`] and [`ObjParser::normals`]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ObjTriangle {
+    pub vertices: [usize; 3],
+    /// Normal of each vertex, if the face gave one for all of them
+    pub normals: Option<[usize; 3]>,
+}
+
+/// Triangles and named groups read from an OBJ file
+///
+/// Reads vertices (`v`), vertex normals (`vn`), faces (`f`, polygons split into triangle fans) and
+/// groups (`g`). Other statements, like texture coordinates and materials, are skipped and counted.
+/// Indices are zero-based here, unlike in the file.
+///
+/// ```
+/// use ray_tracer_challenge::{ObjParser, Point, Ray, Shape, Vector};
+///
+/// let obj = "v -1 1 0\nv -1 -1 0\nv 1 -1 0\nv 1 1 0\nf 1 2 3 4\n";
+/// let parser = ObjParser::parse(obj.as_bytes()).unwrap();
+/// assert_eq!(parser.triangles().len(), 2);
+///
+/// let square = parser.to_group();
+/// let xs = square.intersect(&Ray::new(Point::new(0.5, 0.5, -5.0), Vector::new(0.0, 0.0, 1.0)));
+/// assert_eq!(xs.len(), 1);
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct ObjParser {
+    vertices: Vec<Point>,
+    normals: Vec<Vector>,
+    triangles: Vec<ObjTriangle>,
+    groups: Vec<(String, Vec<ObjTriangle>)>,
+    ignored: usize,
+}
+
+// Parse all the fields as numbers, or fail with the line number
+fn parse_floats(fields: &[&str], line: usize) -> Result<Vec<f64>, ObjError> {
+    fields
+        .iter()
+        .map(|f| f.parse())
+        .collect::<Result<_, _>>()
+        .map_err(|_| ObjError::InvalidLine(line))
+}
+
+// Zero-based index of a one-based (or negative, relative to the end) OBJ index
+fn resolve(index: &str, len: usize, line: usize) -> Result<usize, ObjError> {
+    let i: i64 = index.parse().map_err(|_| ObjError::InvalidLine(line))?;
+    let res = if i < 0 { len as i64 + i } else { i - 1 };
+
+    if (0..len as i64).contains(&res) {
+        Ok(res as usize)
+    } else {
+        Err(ObjError::InvalidIndex(line))
+    }
+}
+
+impl ObjParser {
+    pub fn parse<R: BufRead>(reader: R) -> Result<Self, ObjError> {
+        let mut res = Self::default();
+        // Group the faces are added to, `None` for the default group
+        let mut current: Option<usize> = None;
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let n = i + 1;
+            let fields: Vec<&str> = line.split_whitespace().collect();
+
+            match fields.split_first() {
+                None => (),
+                Some((s, _)) if s.starts_with('#') => (),
+                Some((&"v", args)) => match parse_floats(args, n)?[..] {
+                    // An optional fourth (w) coordinate is ignored
+                    [x, y, z] | [x, y, z, _] => res.vertices.push(Point::new(x, y, z)),
+                    _ => return Err(ObjError::InvalidLine(n)),
+                },
+                Some((&"vn", args)) => match parse_floats(args, n)?[..] {
+                    [x, y, z] => res.normals.push(Vector::new(x, y, z)),
+                    _ => return Err(ObjError::InvalidLine(n)),
+                },
+                Some((&"f", args)) => {
+                    let triangles = res.parse_face(args, n)?;
+                    match current {
+                        Some(g) => res.groups[g].1.extend(triangles),
+                        None => res.triangles.extend(triangles),
+                    }
+                }
+                Some((&"g", [])) => current = None,
+                Some((&"g", names)) => {
+                    let name = names.join(" ");
+                    current = Some(match res.groups.iter().position(|(g, _)| *g == name) {
+                        Some(g) => g,
+                        None => {
+                            res.groups.push((name, Vec::new()));
+                            res.groups.len() - 1
+                        }
+                    });
+                }
+                Some(_) => res.ignored += 1,
+            }
+        }
+
+        Ok(res)
+    }
+
+    pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Self, ObjError> {
+        Self::parse(BufReader::new(File::open(path)?))
+    }
+
+    // Triangle fan of a face's `vertex[/texture][/normal]` references
+    fn parse_face(&self, args: &[&str], line: usize) -> Result<Vec<ObjTriangle>, ObjError> {
+        if args.len() < 3 {
+            return Err(ObjError::InvalidLine(line));
+        }
+
+        let mut vertices = Vec::with_capacity(args.len());
+        let mut normals = Vec::with_capacity(args.len());
+        for arg in args.iter() {
+            let mut refs = arg.split('/');
+            vertices.push(resolve(refs.next().unwrap_or(""), self.vertices.len(), line)?);
+
+            match refs.nth(1) {
+                Some(vn) if !vn.is_empty() => normals.push(resolve(vn, self.normals.len(), line)?),
+                _ => (),
+            }
+        }
+        let has_normals = normals.len() == vertices.len();
+
+        Ok((1..vertices.len() - 1)
+            .map(|i| ObjTriangle {
+                vertices: [vertices[0], vertices[i], vertices[i + 1]],
+                normals: has_normals.then(|| [normals[0], normals[i], normals[i + 1]]),
+            })
+            .collect())
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    pub fn normals(&self) -> &[Vector] {
+        &self.normals
+    }
+
+    /// Triangles outside of any named group
+    pub fn triangles(&self) -> &[ObjTriangle] {
+        &self.triangles
+    }
+
+    /// Triangles of the named group
+    pub fn group(&self, name: &str) -> Option<&[ObjTriangle]> {
+        self.groups.iter().find(|(g, _)| g == name).map(|(_, ts)| &ts[..])
+    }
+
+    /// Names of the groups, in the order they first appear
+    pub fn group_names(&self) -> impl Iterator<Item = &str> {
+        self.groups.iter().map(|(g, _)| g.as_str())
+    }
+
+    /// Number of statements skipped as unsupported
+    pub fn ignored(&self) -> usize {
+        self.ignored
+    }
+
+    fn triangles_to_group(&self, triangles: &[ObjTriangle]) -> Group {
+        let mut group = Group::new();
+
+        for t in triangles.iter() {
+            let [p1, p2, p3] = t.vertices.map(|i| self.vertices[i]);
+
+            match t.normals {
+                Some(normals) => {
+                    let [n1, n2, n3] = normals.map(|i| self.normals[i]);
+                    group.add_child(SmoothTriangle::new(p1, p2, p3, n1, n2, n3));
+                }
+                None => group.add_child(Triangle::new(p1, p2, p3)),
+            }
+        }
+
+        group
+    }
+
+    /// The mesh as a group of triangles, with each named group as a subgroup
+    ///
+    /// Faces with vertex normals become [`SmoothTriangle`]s. For large meshes, the group should be
+    /// [`divide`](crate::Shape::divide)d before rendering.
+    pub fn to_group(&self) -> Group {
+        let mut res = self.triangles_to_group(&self.triangles);
+
+        for (_, triangles) in self.groups.iter() {
+            res.add_child(self.triangles_to_group(triangles));
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ObjError, ObjParser, ObjTriangle, Point, Ray, Shape, Vector};
+
+    fn parse(obj: &str) -> ObjParser {
+        ObjParser::parse(obj.as_bytes()).unwrap()
+    }
+
+    fn flat(a: usize, b: usize, c: usize) -> ObjTriangle {
+        ObjTriangle { vertices: [a, b, c], normals: None }
+    }
+
+    #[test]
+    fn unsupported_statements_are_ignored() {
+        let p = parse(
+            "There was a young lady named Bright\n\
+             who traveled much faster than light.\n\
+             # a comment\n\
+             \n\
+             vt 0.5 0.5\n\
+             usemtl shiny\n",
+        );
+
+        assert_eq!(p.ignored(), 4);
+        assert!(p.vertices().is_empty());
+    }
+
+    #[test]
+    fn vertex_records() {
+        let p = parse("v -1 1 0\nv -1.0000 0.5000 0.0000\nv 1 0 0\nv 1 1 0 1.0\n");
+
+        assert_eq!(
+            p.vertices(),
+            &[
+                Point::new(-1.0, 1.0, 0.0),
+                Point::new(-1.0, 0.5, 0.0),
+                Point::new(1.0, 0.0, 0.0),
+                Point::new(1.0, 1.0, 0.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn vertex_normal_records() {
+        let p = parse("vn 0 0 1\nvn 0.707 0 -0.707\nvn 1 2 3\n");
+
+        assert_eq!(
+            p.normals(),
+            &[Vector::new(0.0, 0.0, 1.0), Vector::new(0.707, 0.0, -0.707), Vector::new(1.0, 2.0, 3.0)]
+        );
+    }
+
+    #[test]
+    fn triangle_faces() {
+        let p = parse("v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\nf 1 2 3\nf 1 3 4\n");
+
+        assert_eq!(p.triangles(), &[flat(0, 1, 2), flat(0, 2, 3)]);
+    }
+
+    #[test]
+    fn polygons_are_triangulated_as_fans() {
+        let p = parse("v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\nv 0 2 0\n\nf 1 2 3 4 5\n");
+
+        assert_eq!(p.triangles(), &[flat(0, 1, 2), flat(0, 2, 3), flat(0, 3, 4)]);
+    }
+
+    #[test]
+    fn faces_with_texture_coordinates_and_relative_indices() {
+        let p = parse("v -1 1 0\nv -1 0 0\nv 1 0 0\nf 1/1 2/2 3/3\nf -3 -2 -1\n");
+
+        assert_eq!(p.triangles(), &[flat(0, 1, 2), flat(0, 1, 2)]);
+    }
+
+    #[test]
+    fn faces_with_normals() {
+        let p = parse(
+            "v 0 1 0\nv -1 0 0\nv 1 0 0\n\
+             vn -1 0 0\nvn 1 0 0\nvn 0 1 0\n\
+             f 1//3 2//1 3//2\n\
+             f 1/0/3 2/102/1 3/14/2\n",
+        );
+        let t = ObjTriangle { vertices: [0, 1, 2], normals: Some([2, 0, 1]) };
+
+        assert_eq!(p.triangles(), &[t, t]);
+    }
+
+    #[test]
+    fn triangles_in_named_groups() {
+        let p = parse(
+            "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\
+             g FirstGroup\nf 1 2 3\n\
+             g SecondGroup\nf 1 3 4\n\
+             g FirstGroup\nf 2 3 4\n",
+        );
+
+        assert!(p.triangles().is_empty());
+        assert_eq!(p.group_names().collect::<Vec<_>>(), vec!["FirstGroup", "SecondGroup"]);
+        assert_eq!(p.group("FirstGroup").unwrap(), &[flat(0, 1, 2), flat(1, 2, 3)]);
+        assert_eq!(p.group("SecondGroup").unwrap(), &[flat(0, 2, 3)]);
+        assert!(p.group("ThirdGroup").is_none());
+    }
+
+    #[test]
+    fn converting_to_a_group() {
+        let p = parse(
+            "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\
+             f 1 2 3\n\
+             g First\nf 1 3 4\n\
+             g Second\nf 1 3 4\n",
+        );
+        let g = p.to_group();
+
+        // The default group's triangle, then a subgroup for each named group
+        assert_eq!(g.len(), 3);
+        assert_eq!(g.children()[1].bounds(), g.children()[2].bounds());
+
+        let r = Ray::new(Point::new(0.5, 0.5, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(g.intersect(&r).len(), 2);
+    }
+
+    #[test]
+    fn smooth_faces_interpolate_normals() {
+        let p = parse("v 0 1 0\nv -1 0 0\nv 1 0 0\nvn 0 1 0\nvn -1 0 0\nvn 1 0 0\nf 1//1 2//2 3//3\n");
+        let g = p.to_group();
+        let r = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = g.intersect(&r);
+        let hit = xs.hit().unwrap();
+        let n = hit.object.normal_at_hit(&r.position(hit.t), hit);
+
+        assert!((n - Vector::new(-0.5547, 0.83205, 0.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn malformed_lines_are_reported() {
+        let cases = [
+            ("v 1 2 3\nv 1 x 3\n", 2),
+            ("v 1 2\n", 1),
+            ("vn 1 2 3 4\n", 1),
+            ("v 1 2 3\nv 1 2 3\n\nf 1 2\n", 4),
+            ("v 1 2 3\nf 1 a 1\n", 2),
+        ];
+
+        for &(obj, line) in cases.iter() {
+            match ObjParser::parse(obj.as_bytes()) {
+                Err(ObjError::InvalidLine(n)) => assert_eq!(n, line, "{:?}", obj),
+                res => panic!("{:?} for {:?}", res, obj),
+            }
+        }
+    }
+
+    #[test]
+    fn missing_vertices_and_normals_are_reported() {
+        let cases = [
+            ("v 1 2 3\nv 1 2 3\nv 1 2 3\nf 1 2 4\n", 4),
+            ("v 1 2 3\nv 1 2 3\nv 1 2 3\nf 0 1 2\n", 4),
+            ("v 1 2 3\nv 1 2 3\nv 1 2 3\nf -4 1 2\n", 4),
+            ("v 1 2 3\nv 1 2 3\nv 1 2 3\nvn 0 0 1\nf 1//1 2//1 3//2\n", 5),
+        ];
+
+        for &(obj, line) in cases.iter() {
+            match ObjParser::parse(obj.as_bytes()) {
+                Err(ObjError::InvalidIndex(n)) => assert_eq!(n, line, "{:?}", obj),
+                res => panic!("{:?} for {:?}", res, obj),
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Minimal dependency-free PNG encoder
+//!
+//! Image data is stored uncompressed (deflate "stored" blocks), trading file size for having no
+//! compression dependency.
+
+use std::io::{Result, Write};
+
+// CRC-32 (ISO 3309) as used by PNG chunks
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+
+    for &b in bytes {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+
+    !crc
+}
+
+// Adler-32 checksum of the zlib stream
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for chunk in bytes.chunks(5552) {
+        for &x in chunk {
+            a += x as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+
+    (b << 16) | a
+}
+
+fn write_chunk<W: Write>(w: &mut W, kind: &[u8; 4], data: &[u8]) -> Result<()> {
+    let mut crc_data = Vec::with_capacity(4 + data.len());
+    crc_data.extend_from_slice(kind);
+    crc_data.extend_from_slice(data);
+
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(&crc_data)?;
+    w.write_all(&crc32(&crc_data).to_be_bytes())
+}
+
+// Wrap raw data into a zlib stream of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65535;
+
+    let mut res = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        res.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+
+        res.push(last as u8);
+        res.extend_from_slice(&len.to_le_bytes());
+        res.extend_from_slice(&(!len).to_le_bytes());
+        res.extend_from_slice(block);
+    }
+    res.extend_from_slice(&adler32(data).to_be_bytes());
+
+    res
+}
+
+/// Write an 8-bit PNG of `width` by `height` pixels
+///
+/// `data` holds the rows top to bottom with `channels` bytes per pixel: 3 for RGB, 4 for RGBA.
+pub(crate) fn write_png<W: Write>(w: &mut W, width: usize, height: usize, channels: usize, data: &[u8]) -> Result<()> {
+    debug_assert!(channels == 3 || channels == 4);
+    debug_assert_eq!(data.len(), width * height * channels);
+
+    w.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    // Bit depth, color type (2: RGB, 6: RGBA), compression, filter, interlace
+    header.extend_from_slice(&[8, if channels == 4 { 6 } else { 2 }, 0, 0, 0]);
+    write_chunk(w, b"IHDR", &header)?;
+
+    // Each row is prefixed by its filter type (0: none)
+    let stride = width * channels;
+    let mut raw = Vec::with_capacity((stride + 1) * height);
+    for row in data.chunks(stride.max(1)).take(height) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+    write_chunk(w, b"IDAT", &zlib_stored(&raw))?;
+
+    write_chunk(w, b"IEND", &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adler32, crc32, write_png};
+
+    #[test]
+    fn checksums_match_reference_values() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn writes_signature_header_and_trailer() {
+        let mut out = Vec::new();
+        write_png(&mut out, 2, 1, 4, &[255, 0, 0, 255, 0, 0, 255, 0]).unwrap();
+
+        assert_eq!(&out[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&out[12..16], b"IHDR");
+        // RGBA color type
+        assert_eq!(out[25], 6);
+        assert_eq!(&out[out.len() - 12..], b"\0\0\0\0IEND\xae\x42\x60\x82");
+    }
+}
@@ -0,0 +1,351 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use super::{BoundingBox, Color, Group, Material, Point, Shape, Sphere, Transform};
+
+// Children per group when dividing the spheres into a bounding volume hierarchy
+const DIVIDE_THRESHOLD: usize = 8;
+
+#[derive(Debug)]
+pub enum PointCloudError {
+    Io(io::Error),
+    /// Line (counting from 1) that is neither `x y z` nor `x y z r g b`, or a PLY vertex that
+    /// does not match the header
+    InvalidLine(usize),
+    /// PLY header that is malformed, not ASCII, or has no vertex positions
+    InvalidHeader,
+}
+
+impl From<io::Error> for PointCloudError {
+    fn from(e: io::Error) -> Self {
+        PointCloudError::Io(e)
+    }
+}
+
+/// Point of a [`PointCloud`], with its color if the file had one
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CloudPoint {
+    pub position: Point,
+    pub color: Option<Color>,
+}
+
+/// Scanned points, to be rendered as tiny spheres
+///
+/// ```
+/// use ray_tracer_challenge::{Material, Point, PointCloud, Ray, Shape, Vector};
+///
+/// let cloud = PointCloud::from_xyz("0 0 0 255 0 0\n1 0 0 0 255 0\n".as_bytes()).unwrap();
+/// let spheres = cloud.to_spheres(0.1, &Material::default());
+///
+/// let xs = spheres.intersect(&Ray::new(Point::new(1.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0)));
+/// assert_eq!(xs.len(), 2);
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct PointCloud {
+    pub points: Vec<CloudPoint>,
+}
+
+impl PointCloud {
+    /// Read points in the XYZ format, one `x y z` per line, optionally followed by `r g b`
+    ///
+    /// Colors are read as 0 to 255 if any color component in the file exceeds 1, and as 0 to 1
+    /// otherwise. Blank lines and lines starting with `#` are skipped.
+    pub fn from_xyz<R: BufRead>(reader: R) -> Result<Self, PointCloudError> {
+        let mut points = Vec::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let values = parse_values(line, i)?;
+            let position = match values[..] {
+                [x, y, z, ..] => Point::new(x, y, z),
+                _ => return Err(PointCloudError::InvalidLine(i + 1)),
+            };
+            let color = match values[3..] {
+                [] => None,
+                [r, g, b] => Some(Color::new(r, g, b)),
+                _ => return Err(PointCloudError::InvalidLine(i + 1)),
+            };
+
+            points.push(CloudPoint { position, color });
+        }
+
+        // The scale is decided once, as a dark point in a 0 to 255 file can look like 0 to 1
+        let bytes = points.iter().filter_map(|p| p.color).any(|c| c.r.max(c.g).max(c.b) > 1.0);
+        if bytes {
+            for c in points.iter_mut().filter_map(|p| p.color.as_mut()) {
+                *c = *c * (1.0 / 255.0);
+            }
+        }
+
+        Ok(Self { points })
+    }
+
+    /// Read points from an XYZ file (see [`from_xyz`](Self::from_xyz))
+    pub fn read_xyz_file<P: AsRef<Path>>(path: P) -> Result<Self, PointCloudError> {
+        Self::from_xyz(BufReader::new(File::open(path)?))
+    }
+
+    /// Read the vertices of an ASCII PLY file, with their `red`, `green` and `blue` if present
+    ///
+    /// Colors stored as integers are read as 0 to 255, and as 0 to 1 otherwise. Other elements
+    /// (such as faces) are skipped, as are binary PLY files, which are reported as
+    /// [`InvalidHeader`](PointCloudError::InvalidHeader).
+    pub fn from_ply<R: BufRead>(reader: R) -> Result<Self, PointCloudError> {
+        let mut lines = reader.lines().enumerate();
+
+        // Elements as (name, count), and the vertex properties as (type, name)
+        let mut elements: Vec<(String, usize)> = Vec::new();
+        let mut properties: Vec<(String, String)> = Vec::new();
+        let mut ascii = false;
+
+        let magic = lines.next().map(|(_, line)| line).transpose()?;
+        if magic.as_deref().map(str::trim) != Some("ply") {
+            return Err(PointCloudError::InvalidHeader);
+        }
+        loop {
+            let line = lines.next().ok_or(PointCloudError::InvalidHeader)?.1?;
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let in_vertex = elements.last().is_some_and(|e| e.0 == "vertex");
+
+            match words[..] {
+                ["end_header"] => break,
+                ["format", format, _] => ascii = format == "ascii",
+                ["element", name, count] => {
+                    let count = count.parse().map_err(|_| PointCloudError::InvalidHeader)?;
+                    elements.push((name.to_string(), count));
+                }
+                ["property", "list", ..] if in_vertex => {
+                    return Err(PointCloudError::InvalidHeader);
+                }
+                ["property", ty, name] if in_vertex => {
+                    properties.push((ty.to_string(), name.to_string()));
+                }
+                _ => (),
+            }
+        }
+
+        let column = |name: &str| properties.iter().position(|p| p.1 == name);
+        let (x, y, z) = match (column("x"), column("y"), column("z")) {
+            (Some(x), Some(y), Some(z)) if ascii => (x, y, z),
+            _ => return Err(PointCloudError::InvalidHeader),
+        };
+        let rgb = match (column("red"), column("green"), column("blue")) {
+            (Some(r), Some(g), Some(b)) => Some((r, g, b)),
+            _ => None,
+        };
+        let scale = match rgb {
+            Some((r, _, _)) if !properties[r].0.starts_with("float") && properties[r].0 != "double" => {
+                1.0 / 255.0
+            }
+            _ => 1.0,
+        };
+
+        // Each element of an ASCII file is a line, so the ones before the vertices can be skipped
+        let mut points = Vec::new();
+        for (name, count) in elements.iter() {
+            for _ in 0..*count {
+                let (i, line) = lines.next().ok_or(PointCloudError::InvalidHeader)?;
+                let line = line?;
+                if name != "vertex" {
+                    continue;
+                }
+
+                let values = parse_values(&line, i)?;
+                if values.len() != properties.len() {
+                    return Err(PointCloudError::InvalidLine(i + 1));
+                }
+
+                let position = Point::new(values[x], values[y], values[z]);
+                let color = rgb.map(|(r, g, b)| Color::new(values[r], values[g], values[b]) * scale);
+                points.push(CloudPoint { position, color });
+            }
+
+            if name == "vertex" {
+                break;
+            }
+        }
+
+        Ok(Self { points })
+    }
+
+    /// Read points from an ASCII PLY file (see [`from_ply`](Self::from_ply))
+    pub fn read_ply_file<P: AsRef<Path>>(path: P) -> Result<Self, PointCloudError> {
+        Self::from_ply(BufReader::new(File::open(path)?))
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn bounds(&self) -> BoundingBox {
+        let mut res = BoundingBox::empty();
+        for p in self.points.iter() {
+            res.add_point(&p.position);
+        }
+        res
+    }
+
+    /// A sphere of `radius` at each point, divided into a bounding volume hierarchy
+    ///
+    /// The spheres use `material`, with its color replaced by the point's own if it has one.
+    ///
+    /// # Panics
+    ///
+    /// If `radius` is not positive and finite.
+    pub fn to_spheres(&self, radius: f64, material: &Material) -> Group {
+        assert!(radius > 0.0 && radius.is_finite(), "sphere radius must be positive and finite");
+
+        let mut group = Group::new();
+
+        for p in self.points.iter() {
+            let transform = Transform::scaling(radius, radius, radius)
+                .then(&Transform::translation(p.position.x, p.position.y, p.position.z));
+            let material = Material { color: p.color.unwrap_or(material.color), ..*material };
+
+            group.add_child(Sphere::new().with_transform(transform).with_material(material));
+        }
+
+        group.divide(DIVIDE_THRESHOLD);
+        group
+    }
+}
+
+// Whitespace separated numbers on line `i` (counting from 0)
+fn parse_values(line: &str, i: usize) -> Result<Vec<f64>, PointCloudError> {
+    line.split_whitespace()
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|_| PointCloudError::InvalidLine(i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::palette::RED;
+    use crate::{Color, Material, Point, PointCloud, PointCloudError, Ray, Shape, Vector};
+
+    #[test]
+    fn reading_points_and_colors() {
+        let xyz = "# scan\n1 2 3\n\n-1.5 0 2e1 255 0 51\n";
+        let cloud = PointCloud::from_xyz(xyz.as_bytes()).unwrap();
+
+        assert_eq!(cloud.len(), 2);
+        assert_eq!(cloud.points[0].position, Point::new(1.0, 2.0, 3.0));
+        assert_eq!(cloud.points[0].color, None);
+        assert_eq!(cloud.points[1].position, Point::new(-1.5, 0.0, 20.0));
+        assert_eq!(cloud.points[1].color, Some(Color::new(1.0, 0.0, 0.2)));
+
+        let cloud = PointCloud::from_xyz("0 0 0 0.5 0.25 1\n".as_bytes()).unwrap();
+        assert_eq!(cloud.points[0].color, Some(Color::new(0.5, 0.25, 1.0)));
+    }
+
+    #[test]
+    fn color_scale_is_decided_for_the_whole_file() {
+        let xyz = "0 0 0 1 0 0\n1 0 0 2 0 0\n2 0 0 255 0 0\n";
+        let cloud = PointCloud::from_xyz(xyz.as_bytes()).unwrap();
+
+        let reds: Vec<f64> = cloud.points.iter().map(|p| p.color.unwrap().r).collect();
+        assert_eq!(reds, vec![1.0 / 255.0, 2.0 / 255.0, 1.0]);
+    }
+
+    #[test]
+    fn reading_ascii_ply() {
+        let ply = "ply\nformat ascii 1.0\ncomment scan\nelement vertex 2\nproperty float x\n\
+                   property float y\nproperty float z\nproperty uchar red\nproperty uchar green\n\
+                   property uchar blue\nelement face 1\nproperty list uchar int vertex_indices\n\
+                   end_header\n1 2 3 255 0 51\n-1 0 0.5 1 0 0\n3 0 1 2\n";
+        let cloud = PointCloud::from_ply(ply.as_bytes()).unwrap();
+
+        assert_eq!(cloud.len(), 2);
+        assert_eq!(cloud.points[0].position, Point::new(1.0, 2.0, 3.0));
+        assert_eq!(cloud.points[0].color, Some(Color::new(1.0, 0.0, 0.2)));
+        assert_eq!(cloud.points[1].position, Point::new(-1.0, 0.0, 0.5));
+        assert_eq!(cloud.points[1].color, Some(Color::new(1.0 / 255.0, 0.0, 0.0)));
+
+        let ply = "ply\nformat ascii 1.0\nelement vertex 1\nproperty double z\nproperty double y\n\
+                   property double x\nend_header\n1 2 3\n";
+        let cloud = PointCloud::from_ply(ply.as_bytes()).unwrap();
+        assert_eq!(cloud.points[0].position, Point::new(3.0, 2.0, 1.0));
+        assert_eq!(cloud.points[0].color, None);
+    }
+
+    #[test]
+    fn invalid_ply_is_reported() {
+        let header = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty float y\n";
+        let cases = [
+            "xyz\n".to_string(),
+            "ply\nformat binary_little_endian 1.0\nelement vertex 0\nproperty float x\n\
+             property float y\nproperty float z\nend_header\n"
+                .to_string(),
+            format!("{}end_header\n1 2\n", header),
+            format!("{}property float z\nend_header\n", header),
+        ];
+        for ply in cases.iter() {
+            match PointCloud::from_ply(ply.as_bytes()) {
+                Err(PointCloudError::InvalidHeader) => (),
+                res => panic!("{:?} for {:?}", res, ply),
+            }
+        }
+
+        let ply = format!("{}property float z\nend_header\n1 2\n", header);
+        assert!(matches!(PointCloud::from_ply(ply.as_bytes()), Err(PointCloudError::InvalidLine(8))));
+    }
+
+    #[test]
+    fn invalid_lines_are_reported() {
+        let cases = ["1 2 3\n1 2\n", "1 2 3\n1 2 3 4\n", "1 2 3\n1 2 x\n"];
+
+        for xyz in cases.iter() {
+            match PointCloud::from_xyz(xyz.as_bytes()) {
+                Err(PointCloudError::InvalidLine(2)) => (),
+                res => panic!("{:?} for {:?}", res, xyz),
+            }
+        }
+    }
+
+    #[test]
+    fn points_become_colored_spheres() {
+        let mut xyz = String::new();
+        for i in 0..20 {
+            xyz += &format!("{} 0 0\n", i);
+        }
+        xyz += "5 3 0 255 0 0\n";
+        let cloud = PointCloud::from_xyz(xyz.as_bytes()).unwrap();
+        let spheres = cloud.to_spheres(0.25, &Material::default());
+
+        assert!(spheres.len() < cloud.len());
+        assert_eq!(spheres.bounds().min, Point::new(-0.25, -0.25, -0.25));
+        assert_eq!(spheres.bounds().max, Point::new(19.25, 3.25, 0.25));
+
+        let r = Ray::new(Point::new(7.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let ts: Vec<f64> = spheres.intersect(&r).iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![4.75, 5.25]);
+
+        let r = Ray::new(Point::new(5.0, 3.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = spheres.intersect(&r);
+        assert_eq!(xs[0].object.material().color, RED);
+    }
+
+    #[test]
+    #[should_panic(expected = "sphere radius")]
+    fn zero_radius_spheres_are_rejected() {
+        let cloud = PointCloud::from_xyz("0 0 0\n".as_bytes()).unwrap();
+        cloud.to_spheres(0.0, &Material::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "sphere radius")]
+    fn negative_radius_spheres_are_rejected() {
+        let cloud = PointCloud::from_xyz("0 0 0\n".as_bytes()).unwrap();
+        cloud.to_spheres(-0.1, &Material::default());
+    }
+}
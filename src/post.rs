@@ -0,0 +1,433 @@
+//! Post-processing effects applied to a rendered canvas
+
+use super::{Canvas, CanvasError, Color, BLACK};
+
+/// An image-space effect producing a new canvas from an existing one
+pub trait Effect {
+    fn apply(&self, canvas: &Canvas) -> Canvas;
+}
+
+/// Composable chain of effects, applied in insertion order
+#[derive(Default)]
+pub struct PostProcess {
+    effects: Vec<Box<dyn Effect>>,
+}
+
+impl PostProcess {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with<E: Effect + 'static>(mut self, effect: E) -> Self {
+        self.effects.push(Box::new(effect));
+        self
+    }
+
+    pub fn push<E: Effect + 'static>(&mut self, effect: E) {
+        self.effects.push(Box::new(effect));
+    }
+}
+
+impl Effect for PostProcess {
+    fn apply(&self, canvas: &Canvas) -> Canvas {
+        let mut res = canvas.clone();
+
+        for effect in self.effects.iter() {
+            res = effect.apply(&res);
+        }
+
+        res
+    }
+}
+
+// Pixel at integer coordinates, clamped to the canvas edges
+fn clamped(canvas: &Canvas, x: isize, y: isize) -> Color {
+    let x = x.clamp(0, canvas.width() as isize - 1) as usize;
+    let y = y.clamp(0, canvas.height() as isize - 1) as usize;
+
+    canvas.pixels()[x + y * canvas.width()]
+}
+
+// Bilinearly filtered pixel at fractional coordinates, clamped to the canvas edges
+fn sample(canvas: &Canvas, x: f64, y: f64) -> Color {
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let fx = x - x0;
+    let fy = y - y0;
+    let (x0, y0) = (x0 as isize, y0 as isize);
+
+    let top = clamped(canvas, x0, y0) * (1.0 - fx) + clamped(canvas, x0 + 1, y0) * fx;
+    let bottom = clamped(canvas, x0, y0 + 1) * (1.0 - fx) + clamped(canvas, x0 + 1, y0 + 1) * fx;
+
+    top * (1.0 - fy) + bottom * fy
+}
+
+// Map every pixel through `f(x, y, color)` into a new canvas
+fn map_pixels<F: Fn(usize, usize, Color) -> Color>(canvas: &Canvas, f: F) -> Canvas {
+    let mut res = canvas.clone();
+    let width = canvas.width();
+
+    for (i, p) in res.pixels_mut().iter_mut().enumerate() {
+        *p = f(i % width, i / width, *p);
+    }
+
+    res
+}
+
+// Offset of pixel (x, y) from the canvas center, normalized so the corners lie at distance one
+fn from_center(canvas: &Canvas, x: usize, y: usize) -> (f64, f64) {
+    let cx = (canvas.width() as f64 - 1.0) / 2.0;
+    let cy = (canvas.height() as f64 - 1.0) / 2.0;
+    let r = (cx * cx + cy * cy).sqrt().max(1.0);
+
+    ((x as f64 - cx) / r, (y as f64 - cy) / r)
+}
+
+/// Lateral chromatic aberration: red and blue channels are scaled radially away from/towards the
+/// center by up to `strength` pixels at the corners
+#[derive(Copy, Clone, Debug)]
+pub struct ChromaticAberration {
+    pub strength: f64,
+}
+
+impl Effect for ChromaticAberration {
+    fn apply(&self, canvas: &Canvas) -> Canvas {
+        map_pixels(canvas, |x, y, p| {
+            let (dx, dy) = from_center(canvas, x, y);
+            let (ox, oy) = (dx * self.strength, dy * self.strength);
+            let (x, y) = (x as f64, y as f64);
+
+            let r = sample(canvas, x + ox, y + oy).r;
+            let b = sample(canvas, x - ox, y - oy).b;
+
+            Color::new(r, p.g, b)
+        })
+    }
+}
+
+/// Darken the image towards the corners
+///
+/// Pixels within `radius` (normalized, corners at one) of the center are untouched, beyond it the
+/// image fades smoothly until it is darkened by `strength` at the corners.
+#[derive(Copy, Clone, Debug)]
+pub struct Vignette {
+    pub strength: f64,
+    pub radius: f64,
+}
+
+impl Effect for Vignette {
+    fn apply(&self, canvas: &Canvas) -> Canvas {
+        map_pixels(canvas, |x, y, p| {
+            let (dx, dy) = from_center(canvas, x, y);
+            let d = (dx * dx + dy * dy).sqrt();
+            let t = ((d - self.radius) / (1.0 - self.radius).max(f64::EPSILON)).clamp(0.0, 1.0);
+            let falloff = t * t * (3.0 - 2.0 * t);
+
+            p * (1.0 - self.strength * falloff)
+        })
+    }
+}
+
+/// Glow around pixels brighter than `threshold` (by luminance)
+///
+/// The bright pass is Gaussian blurred over `radius` pixels and added back scaled by `intensity`.
+#[derive(Copy, Clone, Debug)]
+pub struct Bloom {
+    pub threshold: f64,
+    pub radius: usize,
+    pub intensity: f64,
+}
+
+impl Bloom {
+    pub fn new(threshold: f64, radius: usize, intensity: f64) -> Self {
+        Self { threshold, radius, intensity }
+    }
+
+    fn bright_pass(&self, canvas: &Canvas) -> Canvas {
+        map_pixels(canvas, |_, _, p| {
+            let l = p.luminance();
+            if l > self.threshold {
+                p * ((l - self.threshold) / l)
+            } else {
+                BLACK
+            }
+        })
+    }
+}
+
+impl Effect for Bloom {
+    fn apply(&self, canvas: &Canvas) -> Canvas {
+        let glow = gaussian_blur(&self.bright_pass(canvas), self.radius);
+
+        map_pixels(canvas, |x, y, p| {
+            glow.pixels()[x + y * canvas.width()].scaled_add(self.intensity, &p)
+        })
+    }
+}
+
+// Normalized Gaussian weights for offsets `-radius..=radius`, with the radius at three sigma
+fn gaussian_kernel(radius: usize) -> Vec<f64> {
+    let sigma = (radius as f64 / 3.0).max(f64::EPSILON);
+    let r = radius as isize;
+
+    let weights: Vec<f64> = (-r..=r)
+        .map(|i| (-((i * i) as f64) / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total: f64 = weights.iter().sum();
+
+    weights.into_iter().map(|w| w / total).collect()
+}
+
+// Separable Gaussian blur of the given radius, with edges clamped
+fn gaussian_blur(canvas: &Canvas, radius: usize) -> Canvas {
+    let kernel = gaussian_kernel(radius);
+    let r = radius as isize;
+
+    let convolve = |canvas: &Canvas, dx: isize, dy: isize| {
+        map_pixels(canvas, |x, y, _| {
+            let (x, y) = (x as isize, y as isize);
+
+            (-r..=r).zip(kernel.iter()).fold(BLACK, |sum, (i, &w)| {
+                clamped(canvas, x + i * dx, y + i * dy).scaled_add(w, &sum)
+            })
+        })
+    };
+
+    convolve(&convolve(canvas, 1, 0), 0, 1)
+}
+
+/// Exponential distance fog, composited using a per-pixel depth buffer
+///
+/// Each pixel is blended towards `color` by `1 - e^(-density * depth)`, so infinite depth (rays
+/// that hit nothing) is fully fogged. Since only the depth is needed, fog can be retuned without
+/// re-rendering.
+#[derive(Copy, Clone, Debug)]
+pub struct DepthFog {
+    pub color: Color,
+    pub density: f64,
+}
+
+impl DepthFog {
+    /// # Panics
+    ///
+    /// Panics if `density` is negative or not finite.
+    pub fn new(color: Color, density: f64) -> Self {
+        assert!(
+            density.is_finite() && density >= 0.0,
+            "fog density must be finite and non-negative"
+        );
+
+        Self { color, density }
+    }
+
+    /// Fog `canvas` given row-major `depth`, which must have one entry per pixel
+    pub fn apply(&self, canvas: &Canvas, depth: &[f64]) -> Result<Canvas, CanvasError> {
+        if depth.len() != canvas.pixels().len() {
+            return Err(CanvasError::SizeMismatch);
+        }
+
+        Ok(map_pixels(canvas, |x, y, p| {
+            let depth = depth[x + y * canvas.width()];
+            // `0 * inf` is NaN, so a clear sky is handled separately
+            let visibility = if depth.is_infinite() {
+                if self.density > 0.0 { 0.0 } else { 1.0 }
+            } else {
+                (-self.density * depth).exp()
+            };
+
+            p * visibility + self.color * (1.0 - visibility)
+        }))
+    }
+}
+
+/// Silhouette and crease outlines, detected from per-pixel depth and object-ID buffers
+///
+/// A pixel is drawn in `color` when any of its four neighbours belongs to a different object or
+/// differs in depth by more than `depth_threshold`.
+#[derive(Copy, Clone, Debug)]
+pub struct Outline {
+    pub color: Color,
+    pub depth_threshold: f64,
+}
+
+impl Outline {
+    pub fn new(color: Color, depth_threshold: f64) -> Self {
+        Self { color, depth_threshold }
+    }
+
+    /// Outline `canvas` given row-major `depth` and `ids`, which must have one entry per pixel
+    pub fn apply(&self, canvas: &Canvas, depth: &[f64], ids: &[usize]) -> Result<Canvas, CanvasError> {
+        let n = canvas.pixels().len();
+        if depth.len() != n || ids.len() != n {
+            return Err(CanvasError::SizeMismatch);
+        }
+
+        let (w, h) = (canvas.width(), canvas.height());
+
+        Ok(map_pixels(canvas, |x, y, p| {
+            let i = x + y * w;
+            let neighbours = [
+                (x > 0).then(|| i - 1),
+                (x + 1 < w).then(|| i + 1),
+                (y > 0).then(|| i - w),
+                (y + 1 < h).then(|| i + w),
+            ];
+
+            let edge = neighbours.iter().flatten().any(|&j| {
+                ids[i] != ids[j] || (depth[i] - depth[j]).abs() > self.depth_threshold
+            });
+
+            if edge {
+                self.color
+            } else {
+                p
+            }
+        }))
+    }
+}
+
+impl Canvas {
+    /// Apply a [`Bloom`] with the given parameters
+    pub fn bloom(&self, threshold: f64, radius: usize, intensity: f64) -> Canvas {
+        Bloom::new(threshold, radius, intensity).apply(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bloom, Canvas, ChromaticAberration, Color, Effect, PostProcess, Vignette, BLACK, WHITE};
+
+    #[test]
+    fn vignette_darkens_corners_only() {
+        let canvas = Canvas::with_color(9, 9, WHITE);
+        let res = Vignette { strength: 0.5, radius: 0.5 }.apply(&canvas);
+
+        assert_eq!(*res.pixel(4, 4).unwrap(), WHITE);
+        assert!((res.pixel(0, 0).unwrap().r - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn chromatic_aberration_splits_channels_off_center() {
+        let mut canvas = Canvas::new(9, 9);
+        *canvas.pixel_mut(8, 8).unwrap() = WHITE;
+
+        let same = ChromaticAberration { strength: 0.0 }.apply(&canvas);
+        assert_eq!(same.pixels(), canvas.pixels());
+
+        let res = ChromaticAberration { strength: 1.0 }.apply(&canvas);
+        let corner = *res.pixel(8, 8).unwrap();
+        assert_eq!(corner.g, 1.0);
+        assert!(corner.b < 1.0);
+    }
+
+    #[test]
+    fn bloom_spreads_only_bright_pixels() {
+        let mut canvas = Canvas::with_color(5, 5, Color::gray(0.5));
+        let bloom = Bloom { threshold: 1.0, radius: 1, intensity: 1.0 };
+
+        assert_eq!(bloom.apply(&canvas).pixels(), canvas.pixels());
+
+        *canvas.pixel_mut(2, 2).unwrap() = Color::gray(10.0);
+        let res = bloom.apply(&canvas);
+        assert!(res.pixel(1, 1).unwrap().r > 0.5);
+        assert_eq!(*res.pixel(0, 0).unwrap(), Color::gray(0.5));
+    }
+
+    #[test]
+    fn gaussian_blur_preserves_energy() {
+        let mut canvas = Canvas::new(21, 21);
+        *canvas.pixel_mut(10, 10).unwrap() = Color::gray(9.0);
+
+        let res = super::gaussian_blur(&canvas, 4);
+        let total: f64 = res.pixels().iter().map(|p| p.r).sum();
+
+        assert!((total - 9.0).abs() < 1e-9);
+        assert!(res.pixel(10, 10).unwrap().r > res.pixel(11, 10).unwrap().r);
+        assert!((res.pixel(11, 10).unwrap().r - res.pixel(10, 11).unwrap().r).abs() < 1e-12);
+    }
+
+    #[test]
+    fn canvas_bloom_glows_around_highlights() {
+        let mut canvas = Canvas::new(9, 9);
+        *canvas.pixel_mut(4, 4).unwrap() = Color::gray(5.0);
+
+        let res = canvas.bloom(1.0, 3, 0.5);
+
+        assert!(res.pixel(4, 4).unwrap().r > 5.0);
+        assert!(res.pixel(6, 4).unwrap().r > 0.0);
+        assert_eq!(*res.pixel(0, 8).unwrap(), BLACK);
+    }
+
+    #[test]
+    fn depth_fog_blends_by_distance() {
+        use crate::{CanvasError, DepthFog};
+
+        let canvas = Canvas::with_color(3, 1, WHITE);
+        let fog = DepthFog::new(BLACK, 1.0);
+
+        let res = fog.apply(&canvas, &[0.0, 2.0f64.ln(), f64::INFINITY]).unwrap();
+        assert_eq!(*res.pixel(0, 0).unwrap(), WHITE);
+        assert!((res.pixel(1, 0).unwrap().r - 0.5).abs() < 1e-12);
+        assert_eq!(*res.pixel(2, 0).unwrap(), BLACK);
+
+        assert!(matches!(fog.apply(&canvas, &[0.0]), Err(CanvasError::SizeMismatch)));
+    }
+
+    #[test]
+    fn depth_fog_with_zero_density_is_clear() {
+        use crate::DepthFog;
+
+        let canvas = Canvas::with_color(2, 1, WHITE);
+        let fog = DepthFog::new(BLACK, 0.0);
+
+        let res = fog.apply(&canvas, &[5.0, f64::INFINITY]).unwrap();
+        assert_eq!(*res.pixel(0, 0).unwrap(), WHITE);
+        assert_eq!(*res.pixel(1, 0).unwrap(), WHITE);
+    }
+
+    #[test]
+    fn depth_fog_fully_fogs_infinite_depth() {
+        use crate::DepthFog;
+
+        let canvas = Canvas::with_color(1, 1, WHITE);
+        let fog = DepthFog::new(Color::new(0.5, 0.5, 0.5), 1e-9);
+
+        let res = fog.apply(&canvas, &[f64::INFINITY]).unwrap();
+        assert_eq!(*res.pixel(0, 0).unwrap(), Color::new(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn depth_fog_rejects_negative_density() {
+        crate::DepthFog::new(BLACK, -1.0);
+    }
+
+    #[test]
+    fn outline_marks_object_and_depth_boundaries() {
+        use crate::Outline;
+
+        let canvas = Canvas::with_color(4, 1, WHITE);
+        let outline = Outline::new(BLACK, 0.5);
+
+        let res = outline.apply(&canvas, &[1.0; 4], &[0, 0, 1, 1]).unwrap();
+        let rs: Vec<f64> = res.pixels().iter().map(|p| p.r).collect();
+        assert_eq!(rs, vec![1.0, 0.0, 0.0, 1.0]);
+
+        let res = outline.apply(&canvas, &[1.0, 1.0, 1.2, 5.0], &[0; 4]).unwrap();
+        let rs: Vec<f64> = res.pixels().iter().map(|p| p.r).collect();
+        assert_eq!(rs, vec![1.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn pipeline_applies_effects_in_order() {
+        let canvas = Canvas::with_color(3, 3, WHITE);
+        let vignette = Vignette { strength: 1.0, radius: 0.0 };
+        let post = PostProcess::new().with(vignette).with(vignette);
+
+        let once = vignette.apply(&canvas);
+        let twice = vignette.apply(&once);
+
+        assert_eq!(post.apply(&canvas).pixels(), twice.pixels());
+        assert_eq!(*post.apply(&canvas).pixel(0, 0).unwrap(), BLACK);
+    }
+}
@@ -0,0 +1,79 @@
+//! Numerically stable quadratic root finding, shared by the quadric shapes
+
+/// Real roots of `a t² + b t + c = 0`, in ascending order
+///
+/// Avoids the catastrophic cancellation of the textbook formula when `b² ≫ 4ac` (for example a ray
+/// starting far away from a tiny sphere) by computing the larger-magnitude root first and deriving
+/// the other from the product of the roots, `c / a`.
+///
+/// A degenerate (linear) equation yields its single root twice; `None` is returned when there are
+/// no real roots.
+///
+/// ```
+/// use ray_tracer_challenge::solve_quadratic;
+///
+/// assert_eq!(solve_quadratic(1.0, -3.0, 2.0), Some((1.0, 2.0)));
+/// assert_eq!(solve_quadratic(1.0, 0.0, 1.0), None);
+/// ```
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Option<(f64, f64)> {
+    if a == 0.0 {
+        if b == 0.0 {
+            return None;
+        }
+
+        let t = -c / b;
+        return Some((t, t));
+    }
+
+    let half_b = 0.5 * b;
+
+    solve_reduced_quadratic(a, half_b, c, half_b * half_b - a * c)
+}
+
+/// Real roots of `a t² + 2h t + c = 0` given its reduced discriminant `h² - ac`, in ascending order
+///
+/// For callers that can compute the discriminant more accurately than `h² - ac` (where the two
+/// terms may cancel almost completely), for example geometrically. `a` must not be zero.
+pub fn solve_reduced_quadratic(a: f64, half_b: f64, c: f64, disc: f64) -> Option<(f64, f64)> {
+    if disc < 0.0 {
+        return None;
+    }
+
+    let q = -(half_b + disc.sqrt().copysign(half_b));
+    let (t0, t1) = if q == 0.0 {
+        // b and c both zero, double root at zero
+        (0.0, 0.0)
+    } else {
+        (q / a, c / q)
+    };
+
+    Some(if t0 <= t1 { (t0, t1) } else { (t1, t0) })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solve_quadratic;
+
+    #[test]
+    fn roots_are_sorted() {
+        assert_eq!(solve_quadratic(1.0, 3.0, 2.0), Some((-2.0, -1.0)));
+        assert_eq!(solve_quadratic(-1.0, 3.0, -2.0), Some((1.0, 2.0)));
+    }
+
+    #[test]
+    fn double_and_degenerate_roots() {
+        assert_eq!(solve_quadratic(1.0, -2.0, 1.0), Some((1.0, 1.0)));
+        assert_eq!(solve_quadratic(1.0, 0.0, 0.0), Some((0.0, 0.0)));
+        assert_eq!(solve_quadratic(0.0, 2.0, -4.0), Some((2.0, 2.0)));
+        assert_eq!(solve_quadratic(0.0, 0.0, 1.0), None);
+    }
+
+    #[test]
+    fn small_root_keeps_precision_when_b_dominates() {
+        // Roots 1e-9 and 1e9: the naive formula loses the small root to cancellation
+        let (t0, t1) = solve_quadratic(1.0, -(1e9 + 1e-9), 1.0).unwrap();
+
+        assert!((t0 - 1e-9).abs() / 1e-9 < 1e-12);
+        assert!((t1 - 1e9).abs() / 1e9 < 1e-12);
+    }
+}
@@ -0,0 +1,82 @@
+use super::{Matrix4, Point, Vector};
+
+/// Half-line starting at `origin` and extending along `direction`
+///
+/// ```
+/// use ray_tracer_challenge::{Point, Ray, Vector};
+///
+/// let r = Ray::new(Point::new(2.0, 3.0, 4.0), Vector::new(1.0, 0.0, 0.0));
+///
+/// assert_eq!(r.position(2.5), Point::new(4.5, 3.0, 4.0));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Ray {
+    pub origin: Point,
+    pub direction: Vector,
+}
+
+impl Ray {
+    pub const fn new(origin: Point, direction: Vector) -> Self {
+        Self { origin, direction }
+    }
+
+    /// Point at distance `t` along the ray (in units of the direction's length)
+    #[inline]
+    pub fn position(&self, t: f64) -> Point {
+        self.origin + self.direction * t
+    }
+
+    /// The ray with both origin and direction transformed by `m`
+    ///
+    /// The direction is not re-normalized, so distances along the transformed ray match those
+    /// along the original.
+    #[inline]
+    pub fn transform(&self, m: &Matrix4) -> Self {
+        Self::new(m * self.origin, m * self.direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Ray, Transform, Vector};
+
+    #[test]
+    fn creating_and_querying_a_ray() {
+        let origin = Point::new(1.0, 2.0, 3.0);
+        let direction = Vector::new(4.0, 5.0, 6.0);
+        let r = Ray::new(origin, direction);
+
+        assert_eq!(r.origin, origin);
+        assert_eq!(r.direction, direction);
+    }
+
+    #[test]
+    fn computing_a_point_from_a_distance() {
+        let r = Ray::new(Point::new(2.0, 3.0, 4.0), Vector::new(1.0, 0.0, 0.0));
+
+        assert_eq!(r.position(0.0), Point::new(2.0, 3.0, 4.0));
+        assert_eq!(r.position(1.0), Point::new(3.0, 3.0, 4.0));
+        assert_eq!(r.position(-1.0), Point::new(1.0, 3.0, 4.0));
+        assert_eq!(r.position(2.5), Point::new(4.5, 3.0, 4.0));
+    }
+
+    #[test]
+    fn translating_a_ray() {
+        let r = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(0.0, 1.0, 0.0));
+        let m = Transform::translation(3.0, 4.0, 5.0);
+        let r2 = r.transform(m.matrix());
+
+        assert_eq!(r2.origin, Point::new(4.0, 6.0, 8.0));
+        assert_eq!(r2.direction, Vector::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn scaling_a_ray() {
+        let r = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(0.0, 1.0, 0.0));
+        let m = Transform::scaling(2.0, 3.0, 4.0);
+        let r2 = r.transform(m.matrix());
+
+        assert_eq!(r2.origin, Point::new(2.0, 6.0, 12.0));
+        assert_eq!(r2.direction, Vector::new(0.0, 3.0, 0.0));
+    }
+}
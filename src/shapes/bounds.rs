@@ -0,0 +1,269 @@
+use crate::{Point, Ray, Transform};
+
+use super::cube::intersect_box;
+
+/// Axis-aligned bounding box
+///
+/// Bounds may be infinite along some axes (like those of a [`Plane`](crate::Plane)), and start out
+/// empty, containing nothing until points or other boxes are added.
+///
+/// ```
+/// use ray_tracer_challenge::{BoundingBox, Point, Ray, Vector};
+///
+/// let mut b = BoundingBox::empty();
+/// b.add_point(&Point::new(-1.0, -2.0, 0.0));
+/// b.add_point(&Point::new(1.0, 2.0, 3.0));
+///
+/// assert!(b.contains_point(&Point::new(0.0, 0.0, 1.5)));
+/// assert!(b.intersects(&Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0))));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    pub const fn new(min: Point, max: Point) -> Self {
+        Self { min, max }
+    }
+
+    /// Box containing nothing
+    pub const fn empty() -> Self {
+        Self::new(
+            Point::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        )
+    }
+
+    /// Box containing everything
+    pub const fn infinite() -> Self {
+        Self::new(
+            Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            Point::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    /// Whether the box is non-empty and finite along every axis
+    pub fn is_finite(&self) -> bool {
+        !self.is_empty()
+            && [self.min.x, self.min.y, self.min.z, self.max.x, self.max.y, self.max.z]
+                .iter()
+                .all(|v| v.is_finite())
+    }
+
+    /// Grow the box to contain `point`
+    pub fn add_point(&mut self, point: &Point) {
+        self.min = self.min.min(point);
+        self.max = self.max.max(point);
+    }
+
+    /// Grow the box to contain `other`
+    pub fn merge(&mut self, other: &BoundingBox) {
+        if !other.is_empty() {
+            self.add_point(&other.min);
+            self.add_point(&other.max);
+        }
+    }
+
+    pub fn contains_point(&self, point: &Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    pub fn contains_box(&self, other: &BoundingBox) -> bool {
+        other.is_empty() || (self.contains_point(&other.min) && self.contains_point(&other.max))
+    }
+
+    /// Box containing this box after `transform`
+    ///
+    /// Computed per axis from the extremes of each matrix term rather than by transforming the eight
+    /// corners, which gives the same box but leaves infinite extents unmixed with zero factors
+    /// (otherwise an infinite plane would turn into NaNs under any transformation).
+    pub fn transform(&self, transform: &Transform) -> BoundingBox {
+        if self.is_empty() {
+            return *self;
+        }
+
+        let m = &transform.matrix().data;
+        let min = [self.min.x, self.min.y, self.min.z];
+        let max = [self.max.x, self.max.y, self.max.z];
+        let mut res_min = [0.0; 3];
+        let mut res_max = [0.0; 3];
+
+        for i in 0..3 {
+            res_min[i] = m[i][3];
+            res_max[i] = m[i][3];
+
+            for j in 0..3 {
+                if m[i][j] != 0.0 {
+                    let a = m[i][j] * min[j];
+                    let b = m[i][j] * max[j];
+                    res_min[i] += a.min(b);
+                    res_max[i] += a.max(b);
+                }
+            }
+        }
+
+        BoundingBox::new(
+            Point::new(res_min[0], res_min[1], res_min[2]),
+            Point::new(res_max[0], res_max[1], res_max[2]),
+        )
+    }
+
+    /// The two halves of the box, split across the middle of its longest axis
+    pub fn split(&self) -> (BoundingBox, BoundingBox) {
+        let d = self.max - self.min;
+        let (mut left_max, mut right_min) = (self.max, self.min);
+
+        if d.x >= d.y && d.x >= d.z {
+            left_max.x = self.min.x + 0.5 * d.x;
+            right_min.x = left_max.x;
+        } else if d.y >= d.z {
+            left_max.y = self.min.y + 0.5 * d.y;
+            right_min.y = left_max.y;
+        } else {
+            left_max.z = self.min.z + 0.5 * d.z;
+            right_min.z = left_max.z;
+        }
+
+        (BoundingBox::new(self.min, left_max), BoundingBox::new(right_min, self.max))
+    }
+
+    /// Whether the (infinite) line of the ray passes through the box
+    pub fn intersects(&self, ray: &Ray) -> bool {
+        !self.is_empty() && intersect_box(ray, &self.min, &self.max).is_some()
+    }
+}
+
+impl Default for BoundingBox {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_4, SQRT_2};
+
+    use crate::{Angle, BoundingBox, Point, Ray, Transform, Vector};
+
+    const EPSILON: f64 = 1.0e-12;
+
+    #[test]
+    fn empty_box_contains_nothing() {
+        let b = BoundingBox::empty();
+
+        assert!(b.is_empty());
+        assert!(!b.contains_point(&Point::zero()));
+        assert!(!b.intersects(&Ray::new(Point::zero(), Vector::new(0.0, 0.0, 1.0))));
+        assert_eq!(b.transform(&Transform::translation(1.0, 2.0, 3.0)), b);
+    }
+
+    #[test]
+    fn adding_points_and_boxes() {
+        let mut b = BoundingBox::empty();
+        b.add_point(&Point::new(-5.0, 2.0, 0.0));
+        b.add_point(&Point::new(7.0, 0.0, -3.0));
+        assert_eq!(b, BoundingBox::new(Point::new(-5.0, 0.0, -3.0), Point::new(7.0, 2.0, 0.0)));
+
+        let mut b = BoundingBox::new(Point::new(-5.0, -2.0, 0.0), Point::new(7.0, 4.0, 4.0));
+        b.merge(&BoundingBox::new(Point::new(8.0, -7.0, -2.0), Point::new(14.0, 2.0, 8.0)));
+        b.merge(&BoundingBox::empty());
+        assert_eq!(b, BoundingBox::new(Point::new(-5.0, -7.0, -2.0), Point::new(14.0, 4.0, 8.0)));
+    }
+
+    #[test]
+    fn containing_points_and_boxes() {
+        let b = BoundingBox::new(Point::new(5.0, -2.0, 0.0), Point::new(11.0, 4.0, 7.0));
+
+        assert!(b.contains_point(&Point::new(5.0, -2.0, 0.0)));
+        assert!(b.contains_point(&Point::new(8.0, 1.0, 3.0)));
+        assert!(!b.contains_point(&Point::new(3.0, 0.0, 3.0)));
+        assert!(!b.contains_point(&Point::new(8.0, 1.0, 8.0)));
+
+        let inner = BoundingBox::new(Point::new(6.0, -1.0, 1.0), Point::new(10.0, 3.0, 6.0));
+        assert!(b.contains_box(&inner));
+        assert!(!b.contains_box(&BoundingBox::new(Point::new(4.0, -3.0, -1.0), inner.max)));
+        assert!(!b.contains_box(&BoundingBox::new(inner.min, Point::new(12.0, 5.0, 8.0))));
+    }
+
+    #[test]
+    fn splitting_along_the_longest_axis() {
+        let cases = [
+            ((-1.0, -4.0, -5.0), (9.0, 6.0, 5.0), (4.0, 6.0, 5.0), (4.0, -4.0, -5.0)),
+            ((-1.0, -2.0, -3.0), (9.0, 5.5, 3.0), (4.0, 5.5, 3.0), (4.0, -2.0, -3.0)),
+            ((-1.0, -2.0, -3.0), (5.0, 8.0, 3.0), (5.0, 3.0, 3.0), (-1.0, 3.0, -3.0)),
+            ((-1.0, -2.0, -3.0), (5.0, 3.0, 7.0), (5.0, 3.0, 2.0), (-1.0, -2.0, 2.0)),
+        ];
+
+        for &((x0, y0, z0), (x1, y1, z1), (lx, ly, lz), (rx, ry, rz)) in cases.iter() {
+            let b = BoundingBox::new(Point::new(x0, y0, z0), Point::new(x1, y1, z1));
+            let (left, right) = b.split();
+
+            assert_eq!(left, BoundingBox::new(b.min, Point::new(lx, ly, lz)));
+            assert_eq!(right, BoundingBox::new(Point::new(rx, ry, rz), b.max));
+        }
+    }
+
+    #[test]
+    fn only_bounded_boxes_are_finite() {
+        assert!(BoundingBox::new(Point::zero(), Point::one()).is_finite());
+        assert!(!BoundingBox::new(Point::zero(), Point::new(1.0, f64::INFINITY, 1.0)).is_finite());
+        assert!(!BoundingBox::infinite().is_finite());
+        assert!(!BoundingBox::empty().is_finite());
+    }
+
+    #[test]
+    fn transforming_a_box() {
+        let b = BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0));
+        let t = Transform::rotation_y(Angle::radians(FRAC_PI_4))
+            .then(&Transform::rotation_x(Angle::radians(FRAC_PI_4)));
+        let res = b.transform(&t);
+
+        let (a, b) = (SQRT_2, 1.0 + FRAC_1_SQRT_2);
+        assert!((res.min - Point::new(-a, -b, -b)).length() < EPSILON);
+        assert!((res.max - Point::new(a, b, b)).length() < EPSILON);
+    }
+
+    #[test]
+    fn transforming_an_infinite_box() {
+        let b = BoundingBox::new(
+            Point::new(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Point::new(f64::INFINITY, 0.0, f64::INFINITY),
+        );
+
+        let res = b.transform(&Transform::translation(0.0, 2.0, 0.0));
+        assert_eq!(res.min, Point::new(f64::NEG_INFINITY, 2.0, f64::NEG_INFINITY));
+        assert_eq!(res.max, Point::new(f64::INFINITY, 2.0, f64::INFINITY));
+
+        let res = b.transform(&Transform::rotation_x(Angle::radians(FRAC_PI_4)));
+        assert_eq!(res, BoundingBox::infinite());
+    }
+
+    #[test]
+    fn intersecting_rays_with_a_box() {
+        let b = BoundingBox::new(Point::new(5.0, -2.0, 0.0), Point::new(11.0, 4.0, 7.0));
+        let cases = [
+            (Point::new(15.0, 1.0, 2.0), Vector::new(-1.0, 0.0, 0.0), true),
+            (Point::new(-5.0, -1.0, 4.0), Vector::new(1.0, 0.0, 0.0), true),
+            (Point::new(7.0, 6.0, 5.0), Vector::new(0.0, -1.0, 0.0), true),
+            (Point::new(9.0, 0.0, 9.0), Vector::new(0.0, 0.0, -1.0), true),
+            (Point::new(8.0, 2.0, 12.0), Vector::new(0.0, 0.0, -1.0), true),
+            (Point::new(6.0, 0.0, 5.0), Vector::new(1.0, 1.0, 1.0), true),
+            (Point::new(9.0, -1.0, -8.0), Vector::new(2.0, 4.0, 6.0), false),
+            (Point::new(9.0, -1.0, -8.0), Vector::new(1.0, 0.0, 0.0), false),
+            (Point::new(12.0, 5.0, 4.0), Vector::new(-1.0, 0.0, 0.0), false),
+        ];
+
+        for &(origin, direction, hit) in cases.iter() {
+            let r = Ray::new(origin, direction.normalize());
+            assert_eq!(b.intersects(&r), hit, "{:?}", r);
+        }
+    }
+}
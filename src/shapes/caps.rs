@@ -0,0 +1,32 @@
+//! End cap intersection shared by the truncated quadrics
+
+use crate::{Intersection, Intersections, Ray, Shape};
+
+use super::EPSILON;
+
+// Whether the point at `t` along the ray lies within `radius` of the y axis
+fn check_cap(ray: &Ray, t: f64, radius: f64) -> bool {
+    let x = ray.origin.x + t * ray.direction.x;
+    let z = ray.origin.z + t * ray.direction.z;
+
+    x * x + z * z <= radius * radius
+}
+
+/// Add the intersection with the disc of `radius` in the plane at height `y`, if any
+pub(super) fn intersect_cap<'a>(
+    shape: &'a dyn Shape,
+    ray: &Ray,
+    y: f64,
+    radius: f64,
+    xs: &mut Intersections<'a>,
+) {
+    // Rays parallel to the cap can't hit it
+    if ray.direction.y.abs() < EPSILON {
+        return;
+    }
+
+    let t = (y - ray.origin.y) / ray.direction.y;
+    if check_cap(ray, t, radius) {
+        xs.push(Intersection::new(t, shape));
+    }
+}
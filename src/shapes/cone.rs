@@ -0,0 +1,227 @@
+use crate::{
+    solve_reduced_quadratic, BoundingBox, Intersection, Intersections, Point, Ray, Shape, ShapeData, Vector,
+};
+
+use super::caps::intersect_cap;
+use super::EPSILON;
+
+/// Double-napped cone around the y axis, with its apex at the origin
+///
+/// The radius at any height equals the distance from the apex, so the unit cone widens with slope
+/// one. It extends infinitely in both directions unless truncated between `minimum` and `maximum`
+/// (both exclusive), in which case it can also be `closed` with end caps. Only finite ends are
+/// capped.
+#[derive(Clone, Debug)]
+pub struct Cone {
+    data: ShapeData,
+    pub minimum: f64,
+    pub maximum: f64,
+    pub closed: bool,
+}
+
+impl Cone {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Cone truncated between `minimum` and `maximum`, optionally capped at both ends
+    pub fn truncated(minimum: f64, maximum: f64, closed: bool) -> Self {
+        Self { minimum, maximum, closed, ..Default::default() }
+    }
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Self {
+            data: Default::default(),
+            minimum: f64::NEG_INFINITY,
+            maximum: f64::INFINITY,
+            closed: false,
+        }
+    }
+}
+
+impl Shape for Cone {
+    fn data(&self) -> &ShapeData {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut ShapeData {
+        &mut self.data
+    }
+
+    fn local_intersect<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a>) {
+        let (o, d) = (ray.origin, ray.direction);
+
+        let a = d.x * d.x - d.y * d.y + d.z * d.z;
+        let b = 2.0 * (o.x * d.x - o.y * d.y + o.z * d.z);
+        let c = o.x * o.x - o.y * o.y + o.z * o.z;
+
+        let mut push_side = |t: f64| {
+            let y = o.y + t * d.y;
+            if self.minimum < y && y < self.maximum {
+                xs.push(Intersection::new(t, self));
+            }
+        };
+
+        if a.abs() < EPSILON {
+            // The ray is parallel to one of the halves, crossing the other just once
+            if b.abs() >= EPSILON {
+                push_side(-c / b);
+            }
+        } else {
+            // Rays grazing the surface through the apex round to a slightly negative discriminant
+            let half_b = 0.5 * b;
+            let disc = half_b * half_b - a * c;
+
+            if disc > -EPSILON {
+                if let Some((t0, t1)) = solve_reduced_quadratic(a, half_b, c, disc.max(0.0)) {
+                    push_side(t0);
+                    push_side(t1);
+                }
+            }
+        }
+
+        // An end left at infinity has no cap, even when closed
+        if self.closed {
+            if self.minimum.is_finite() {
+                intersect_cap(self, ray, self.minimum, self.minimum.abs(), xs);
+            }
+            if self.maximum.is_finite() {
+                intersect_cap(self, ray, self.maximum, self.maximum.abs(), xs);
+            }
+        }
+    }
+
+    fn local_normal_at(&self, point: &Point) -> Vector {
+        let dist = point.x * point.x + point.z * point.z;
+
+        if dist < self.maximum * self.maximum && point.y >= self.maximum - EPSILON {
+            Vector::new(0.0, 1.0, 0.0)
+        } else if dist < self.minimum * self.minimum && point.y <= self.minimum + EPSILON {
+            Vector::new(0.0, -1.0, 0.0)
+        } else {
+            let y = dist.sqrt();
+            let y = if point.y > 0.0 { -y } else { y };
+
+            Vector::new(point.x, y, point.z)
+        }
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let limit = self.minimum.abs().max(self.maximum.abs());
+
+        BoundingBox::new(Point::new(-limit, self.minimum, -limit), Point::new(limit, self.maximum, limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_1_SQRT_2, SQRT_2};
+
+    use crate::{Cone, Intersections, Point, Ray, Shape, Vector};
+
+    const EPSILON: f64 = 1.0e-5;
+
+    fn ts(c: &Cone, origin: Point, direction: Vector) -> Vec<f64> {
+        let mut xs = Intersections::new();
+        c.local_intersect(&Ray::new(origin, direction.normalize()), &mut xs);
+        xs.iter().map(|i| i.t).collect()
+    }
+
+    #[test]
+    fn ray_intersects_cone() {
+        let c = Cone::new();
+        let cases = [
+            (Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0), 5.0, 5.0),
+            (Point::new(0.0, 0.0, -5.0), Vector::new(1.0, 1.0, 1.0), 8.66025, 8.66025),
+            (Point::new(1.0, 1.0, -5.0), Vector::new(-0.5, -1.0, 1.0), 4.55006, 49.44994),
+        ];
+
+        for &(origin, direction, t0, t1) in cases.iter() {
+            let xs = ts(&c, origin, direction);
+
+            assert_eq!(xs.len(), 2);
+            assert!((xs[0] - t0).abs() < EPSILON, "{} != {}", xs[0], t0);
+            assert!((xs[1] - t1).abs() < EPSILON, "{} != {}", xs[1], t1);
+        }
+    }
+
+    #[test]
+    fn ray_parallel_to_one_half() {
+        // The book gives 0.35355 here, but solving `b t + c = 0` puts the hit at 1/√2
+        let xs = ts(&Cone::new(), Point::new(0.0, 0.0, -1.0), Vector::new(0.0, 1.0, 1.0));
+
+        assert_eq!(xs.len(), 1);
+        assert!((xs[0] - FRAC_1_SQRT_2).abs() < EPSILON);
+    }
+
+    #[test]
+    fn truncated_cone_misses_outside_its_bounds() {
+        let c = Cone::truncated(-0.5, 0.5, false);
+
+        assert!(ts(&c, Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 1.0, 0.0)).is_empty());
+        assert!(ts(&c, Point::new(0.0, 2.0, -5.0), Vector::new(0.0, 0.0, 1.0)).is_empty());
+        assert_eq!(ts(&c, Point::new(0.0, 0.25, -5.0), Vector::new(0.0, 0.0, 1.0)).len(), 2);
+    }
+
+    #[test]
+    fn intersecting_the_end_caps() {
+        let c = Cone::truncated(-0.5, 0.5, true);
+        let cases = [
+            (Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 1.0, 0.0), 0),
+            (Point::new(0.0, 0.0, -0.25), Vector::new(0.0, 1.0, 1.0), 2),
+            (Point::new(0.0, 0.0, -0.25), Vector::new(0.0, 1.0, 0.0), 4),
+        ];
+
+        for &(origin, direction, count) in cases.iter() {
+            assert_eq!(ts(&c, origin, direction).len(), count);
+        }
+    }
+
+    #[test]
+    fn closed_cones_only_cap_finite_ends() {
+        let origin = Point::new(0.0, 0.0, -5.0);
+        let direction = Vector::new(1.0, 2.0, 1.0);
+
+        let mut c = Cone::new();
+        c.closed = true;
+        let xs = ts(&c, origin, direction);
+        assert_eq!(xs, ts(&Cone::new(), origin, direction));
+        assert!(xs.iter().all(|t| t.is_finite()));
+        assert_eq!(c.bounds(), Cone::new().bounds());
+
+        let c = Cone::truncated(f64::NEG_INFINITY, 1.0, true);
+        let xs = ts(&c, Point::new(0.0, 5.0, 0.5), Vector::new(0.0, -1.0, 0.0));
+        assert_eq!(xs.len(), 3);
+        assert!(xs.iter().all(|t| t.is_finite()));
+    }
+
+    #[test]
+    fn bounds_of_infinite_and_truncated_cones() {
+        let b = Cone::new().bounds();
+        assert_eq!(b.min, Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY));
+        assert_eq!(b.max, Point::new(f64::INFINITY, f64::INFINITY, f64::INFINITY));
+
+        let b = Cone::truncated(-5.0, 3.0, false).bounds();
+        assert_eq!(b.min, Point::new(-5.0, -5.0, -5.0));
+        assert_eq!(b.max, Point::new(5.0, 3.0, 5.0));
+    }
+
+    #[test]
+    fn normals_on_the_sides() {
+        let c = Cone::new();
+
+        assert_eq!(c.local_normal_at(&Point::new(0.0, 0.0, 0.0)), Vector::new(0.0, 0.0, 0.0));
+        assert_eq!(c.local_normal_at(&Point::new(1.0, 1.0, 1.0)), Vector::new(1.0, -SQRT_2, 1.0));
+        assert_eq!(c.local_normal_at(&Point::new(-1.0, -1.0, 0.0)), Vector::new(-1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn normals_on_the_end_caps() {
+        let c = Cone::truncated(-1.0, 2.0, true);
+
+        assert_eq!(c.local_normal_at(&Point::new(0.5, 2.0, 0.0)), Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(c.local_normal_at(&Point::new(0.0, -1.0, 0.5)), Vector::new(0.0, -1.0, 0.0));
+    }
+}
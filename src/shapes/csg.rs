@@ -0,0 +1,279 @@
+use std::ptr;
+
+use crate::{BoundingBox, Intersections, Point, Ray, Shape, ShapeData, Transform, Vector};
+
+/// How a [`Csg`] combines its two shapes
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CsgOperation {
+    /// Everything inside either shape
+    Union,
+    /// Only what is inside both shapes
+    Intersection,
+    /// What is inside the left shape but not the right
+    Difference,
+}
+
+impl CsgOperation {
+    /// Whether an intersection is kept, given whether it is with the left shape (`left_hit`) and
+    /// whether it is inside the left and right shapes
+    pub fn allows(self, left_hit: bool, in_left: bool, in_right: bool) -> bool {
+        match self {
+            CsgOperation::Union => (left_hit && !in_right) || (!left_hit && !in_left),
+            CsgOperation::Intersection => (left_hit && in_right) || (!left_hit && in_left),
+            CsgOperation::Difference => (left_hit && !in_right) || (!left_hit && in_left),
+        }
+    }
+}
+
+/// Constructive solid geometry, two shapes combined by a set operation
+///
+/// Like a [`Group`](crate::Group), the transformation of the combined shape applies on top of each
+/// child's own. Either child can itself be a group or another `Csg`.
+///
+/// ```
+/// use ray_tracer_challenge::{Csg, CsgOperation, Cube, Point, Ray, Shape, Sphere, Transform, Vector};
+///
+/// // A cube with a spherical dent
+/// let dented = Csg::new(
+///     CsgOperation::Difference,
+///     Cube::new(),
+///     Sphere::new().with_transform(Transform::translation(0.0, 0.0, -1.5)),
+/// );
+///
+/// let xs = dented.intersect(&Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0)));
+/// assert_eq!(xs.iter().map(|i| i.t).collect::<Vec<_>>(), vec![4.5, 6.0]);
+/// ```
+#[derive(Debug)]
+pub struct Csg {
+    data: ShapeData,
+    operation: CsgOperation,
+    left: Box<dyn Shape>,
+    right: Box<dyn Shape>,
+}
+
+impl Csg {
+    pub fn new(operation: CsgOperation, left: impl Shape + 'static, right: impl Shape + 'static) -> Self {
+        let mut res = Self {
+            data: Default::default(),
+            operation,
+            left: Box::new(left),
+            right: Box::new(right),
+        };
+        res.update_children();
+        res
+    }
+
+    pub fn operation(&self) -> CsgOperation {
+        self.operation
+    }
+
+    pub fn left(&self) -> &dyn Shape {
+        &*self.left
+    }
+
+    pub fn right(&self) -> &dyn Shape {
+        &*self.right
+    }
+
+    fn update_children(&mut self) {
+        let world = self.transform().then(self.parent_transform());
+
+        self.left.set_parent_transform(world);
+        self.right.set_parent_transform(world);
+    }
+
+    // The intersections (with either child, sorted) which are on the combined surface
+    fn filter_intersections<'a>(&self, xs: &Intersections<'a>) -> Intersections<'a> {
+        let mut in_left = false;
+        let mut in_right = false;
+        let mut res = Intersections::new();
+
+        for i in xs {
+            let left_hit = self.left.includes(i.object);
+
+            if self.operation.allows(left_hit, in_left, in_right) {
+                res.push(*i);
+            }
+
+            if left_hit {
+                in_left = !in_left;
+            } else {
+                in_right = !in_right;
+            }
+        }
+
+        res
+    }
+}
+
+impl Shape for Csg {
+    fn data(&self) -> &ShapeData {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut ShapeData {
+        &mut self.data
+    }
+
+    fn set_transform(&mut self, transform: Transform) {
+        self.data.transform = transform;
+        self.update_children();
+    }
+
+    fn set_parent_transform(&mut self, parent: Transform) {
+        self.data.parent = parent;
+        self.update_children();
+    }
+
+    fn includes(&self, other: &dyn Shape) -> bool {
+        ptr::addr_eq(self, other) || self.left.includes(other) || self.right.includes(other)
+    }
+
+    fn divide(&mut self, threshold: usize) {
+        self.left.divide(threshold);
+        self.right.divide(threshold);
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut res = self.left.parent_space_bounds();
+        res.merge(&self.right.parent_space_bounds());
+        res
+    }
+
+    fn local_intersect<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a>) {
+        let mut children_xs = Intersections::new();
+        self.left.intersect_into(ray, &mut children_xs);
+        self.right.intersect_into(ray, &mut children_xs);
+
+        for i in &self.filter_intersections(&children_xs) {
+            xs.push(*i);
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Always, as intersections are with the children, never the combined shape.
+    fn local_normal_at(&self, _point: &Point) -> Vector {
+        panic!("CSG shapes have no surface normal")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Csg, CsgOperation, Cube, Intersection, Intersections, Point, Ray, Shape, Sphere, Transform, Vector,
+    };
+
+    #[test]
+    fn children_are_transformed_with_the_csg() {
+        let c = Csg::new(CsgOperation::Union, Sphere::new(), Cube::new())
+            .with_transform(Transform::translation(1.0, 0.0, 0.0));
+
+        assert_eq!(c.operation(), CsgOperation::Union);
+        assert_eq!(*c.left().parent_transform(), Transform::translation(1.0, 0.0, 0.0));
+        assert_eq!(*c.right().parent_transform(), Transform::translation(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn bounds_contain_both_children() {
+        let c = Csg::new(
+            CsgOperation::Difference,
+            Sphere::new(),
+            Sphere::new().with_transform(Transform::translation(2.0, 3.0, 4.0)),
+        );
+        let b = c.bounds();
+
+        assert_eq!(b.min, Point::new(-1.0, -1.0, -1.0));
+        assert_eq!(b.max, Point::new(3.0, 4.0, 5.0));
+    }
+
+    #[test]
+    fn operation_rules() {
+        use CsgOperation::*;
+
+        // (left_hit, in_left, in_right) and whether union, intersection, difference allow it
+        let cases = [
+            ((true, true, true), [false, true, false]),
+            ((true, true, false), [true, false, true]),
+            ((true, false, true), [false, true, false]),
+            ((true, false, false), [true, false, true]),
+            ((false, true, true), [false, true, true]),
+            ((false, true, false), [false, true, true]),
+            ((false, false, true), [true, false, false]),
+            ((false, false, false), [true, false, false]),
+        ];
+
+        for &((left_hit, in_left, in_right), expected) in cases.iter() {
+            for (op, &allowed) in [Union, Intersection, Difference].iter().zip(expected.iter()) {
+                let got = op.allows(left_hit, in_left, in_right);
+                assert_eq!(got, allowed, "{:?} {:?}", op, (left_hit, in_left, in_right));
+            }
+        }
+    }
+
+    #[test]
+    fn filtering_a_list_of_intersections() {
+        let cases = [
+            (CsgOperation::Union, [0, 3]),
+            (CsgOperation::Intersection, [1, 2]),
+            (CsgOperation::Difference, [0, 1]),
+        ];
+
+        for &(op, [x0, x1]) in cases.iter() {
+            let c = Csg::new(op, Sphere::new(), Cube::new());
+            let (s1, s2) = (c.left(), c.right());
+            let all = [
+                Intersection::new(1.0, s1),
+                Intersection::new(2.0, s2),
+                Intersection::new(3.0, s1),
+                Intersection::new(4.0, s2),
+            ];
+            let xs = c.filter_intersections(&Intersections::from(all.to_vec()));
+
+            assert_eq!(xs.len(), 2);
+            assert_eq!((xs[0], xs[1]), (all[x0], all[x1]));
+        }
+    }
+
+    #[test]
+    fn ray_misses_csg() {
+        let c = Csg::new(CsgOperation::Union, Sphere::new(), Cube::new());
+        let r = Ray::new(Point::new(0.0, 2.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert!(c.intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn ray_hits_csg() {
+        let c = Csg::new(
+            CsgOperation::Union,
+            Sphere::new(),
+            Sphere::new().with_transform(Transform::translation(0.0, 0.0, 0.5)),
+        );
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = c.intersect(&r);
+
+        assert_eq!(xs.len(), 2);
+        assert_eq!((xs[0].t, xs[1].t), (4.0, 6.5));
+        assert!(std::ptr::addr_eq(xs[0].object, c.left()));
+        assert!(std::ptr::addr_eq(xs[1].object, c.right()));
+    }
+
+    #[test]
+    fn nested_csg_combines_filtered_intersections() {
+        // A capsule-like union from z = -1 to 2, with a hollow of radius 0.5 at the origin
+        let inner = Csg::new(
+            CsgOperation::Union,
+            Cube::new(),
+            Sphere::new().with_transform(Transform::translation(0.0, 0.0, 1.0)),
+        );
+        let hollow = Sphere::new().with_transform(Transform::scaling(0.5, 0.5, 0.5));
+        let c = Csg::new(CsgOperation::Difference, inner, hollow);
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        let ts: Vec<f64> = c.intersect(&r).iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![4.0, 4.5, 5.5, 7.0]);
+        assert!(c.includes(c.left()));
+        assert!(!c.left().includes(c.right()));
+    }
+}
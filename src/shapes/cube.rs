@@ -0,0 +1,144 @@
+use crate::{BoundingBox, Intersection, Intersections, Point, Ray, Shape, ShapeData, Vector};
+
+use super::EPSILON;
+
+/// Axis-aligned cube spanning -1 to 1 on each axis
+#[derive(Clone, Default, Debug)]
+pub struct Cube {
+    data: ShapeData,
+}
+
+impl Cube {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+/// Distances at which a ray component enters and leaves the slab `min..=max` of one axis
+pub(crate) fn check_axis(origin: f64, direction: f64, min: f64, max: f64) -> (f64, f64) {
+    let tmin_numerator = min - origin;
+    let tmax_numerator = max - origin;
+
+    let (tmin, tmax) = if direction.abs() >= EPSILON {
+        (tmin_numerator / direction, tmax_numerator / direction)
+    } else {
+        (tmin_numerator * f64::INFINITY, tmax_numerator * f64::INFINITY)
+    };
+
+    if tmin > tmax {
+        (tmax, tmin)
+    } else {
+        (tmin, tmax)
+    }
+}
+
+/// Distances at which a ray enters and leaves the box `min..=max`, if it hits it at all
+pub(crate) fn intersect_box(ray: &Ray, min: &Point, max: &Point) -> Option<(f64, f64)> {
+    let (xtmin, xtmax) = check_axis(ray.origin.x, ray.direction.x, min.x, max.x);
+    let (ytmin, ytmax) = check_axis(ray.origin.y, ray.direction.y, min.y, max.y);
+    let (ztmin, ztmax) = check_axis(ray.origin.z, ray.direction.z, min.z, max.z);
+
+    let tmin = xtmin.max(ytmin).max(ztmin);
+    let tmax = xtmax.min(ytmax).min(ztmax);
+
+    (tmin <= tmax).then_some((tmin, tmax))
+}
+
+impl Shape for Cube {
+    fn data(&self) -> &ShapeData {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut ShapeData {
+        &mut self.data
+    }
+
+    fn local_intersect<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a>) {
+        let min = Point::new(-1.0, -1.0, -1.0);
+        let max = Point::new(1.0, 1.0, 1.0);
+
+        if let Some((tmin, tmax)) = intersect_box(ray, &min, &max) {
+            xs.push(Intersection::new(tmin, self));
+            xs.push(Intersection::new(tmax, self));
+        }
+    }
+
+    fn local_normal_at(&self, point: &Point) -> Vector {
+        let (x, y, z) = (point.x.abs(), point.y.abs(), point.z.abs());
+        let max = x.max(y).max(z);
+
+        if max == x {
+            Vector::new(point.x, 0.0, 0.0)
+        } else if max == y {
+            Vector::new(0.0, point.y, 0.0)
+        } else {
+            Vector::new(0.0, 0.0, point.z)
+        }
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cube, Point, Ray, Shape, Vector};
+
+    #[test]
+    fn ray_intersects_each_face() {
+        let c = Cube::new();
+        let cases = [
+            (Point::new(5.0, 0.5, 0.0), Vector::new(-1.0, 0.0, 0.0), 4.0, 6.0),
+            (Point::new(-5.0, 0.5, 0.0), Vector::new(1.0, 0.0, 0.0), 4.0, 6.0),
+            (Point::new(0.5, 5.0, 0.0), Vector::new(0.0, -1.0, 0.0), 4.0, 6.0),
+            (Point::new(0.5, -5.0, 0.0), Vector::new(0.0, 1.0, 0.0), 4.0, 6.0),
+            (Point::new(0.5, 0.0, 5.0), Vector::new(0.0, 0.0, -1.0), 4.0, 6.0),
+            (Point::new(0.5, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0), 4.0, 6.0),
+            (Point::new(0.0, 0.5, 0.0), Vector::new(0.0, 0.0, 1.0), -1.0, 1.0),
+        ];
+
+        for &(origin, direction, t1, t2) in cases.iter() {
+            let xs = c.intersect(&Ray::new(origin, direction));
+
+            assert_eq!(xs.len(), 2);
+            assert_eq!((xs[0].t, xs[1].t), (t1, t2));
+        }
+    }
+
+    #[test]
+    fn ray_misses_cube() {
+        let c = Cube::new();
+        let cases = [
+            (Point::new(-2.0, 0.0, 0.0), Vector::new(0.2673, 0.5345, 0.8018)),
+            (Point::new(0.0, -2.0, 0.0), Vector::new(0.8018, 0.2673, 0.5345)),
+            (Point::new(0.0, 0.0, -2.0), Vector::new(0.5345, 0.8018, 0.2673)),
+            (Point::new(2.0, 0.0, 2.0), Vector::new(0.0, 0.0, -1.0)),
+            (Point::new(0.0, 2.0, 2.0), Vector::new(0.0, -1.0, 0.0)),
+            (Point::new(2.0, 2.0, 0.0), Vector::new(-1.0, 0.0, 0.0)),
+        ];
+
+        for &(origin, direction) in cases.iter() {
+            assert!(c.intersect(&Ray::new(origin, direction)).is_empty());
+        }
+    }
+
+    #[test]
+    fn normal_on_the_surface() {
+        let c = Cube::new();
+        let cases = [
+            (Point::new(1.0, 0.5, -0.8), Vector::new(1.0, 0.0, 0.0)),
+            (Point::new(-1.0, -0.2, 0.9), Vector::new(-1.0, 0.0, 0.0)),
+            (Point::new(-0.4, 1.0, -0.1), Vector::new(0.0, 1.0, 0.0)),
+            (Point::new(0.3, -1.0, -0.7), Vector::new(0.0, -1.0, 0.0)),
+            (Point::new(-0.6, 0.3, 1.0), Vector::new(0.0, 0.0, 1.0)),
+            (Point::new(0.4, 0.4, -1.0), Vector::new(0.0, 0.0, -1.0)),
+            (Point::new(1.0, 1.0, 1.0), Vector::new(1.0, 0.0, 0.0)),
+            (Point::new(-1.0, -1.0, -1.0), Vector::new(-1.0, 0.0, 0.0)),
+        ];
+
+        for (point, normal) in cases.iter() {
+            assert_eq!(c.local_normal_at(point), *normal);
+        }
+    }
+}
@@ -0,0 +1,400 @@
+use std::ptr;
+
+use crate::{BoundingBox, Intersections, Point, Ray, Shape, ShapeData, Transform, Vector};
+
+type Children = Vec<Box<dyn Shape>>;
+
+/// Collection of shapes transformed as one
+///
+/// The group's transformation applies on top of each child's own. Children know the combined
+/// transformation of the groups containing them, so their normals can be computed directly from
+/// a world space point, however deeply they are nested.
+///
+/// Large groups (such as meshes) should be reorganized with [`divide`](Shape::divide) once built,
+/// so that rays only test the children whose bounds they pass through.
+///
+/// ```
+/// use ray_tracer_challenge::{Group, Point, Ray, Shape, Sphere, Transform, Vector};
+///
+/// let g = Group::new()
+///     .with_child(Sphere::new().with_transform(Transform::translation(5.0, 0.0, 0.0)))
+///     .with_transform(Transform::scaling(2.0, 2.0, 2.0));
+///
+/// let xs = g.intersect(&Ray::new(Point::new(10.0, 0.0, -10.0), Vector::new(0.0, 0.0, 1.0)));
+/// assert_eq!(xs.len(), 2);
+/// assert_eq!(xs[0].object.normal_at(&Point::new(10.0, 0.0, -2.0)), Vector::new(0.0, 0.0, -1.0));
+/// ```
+#[derive(Default, Debug)]
+pub struct Group {
+    data: ShapeData,
+    children: Children,
+    // Children can't change once added, so their combined bounds are kept up to date as they are
+    bounds: BoundingBox,
+}
+
+impl Group {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn children(&self) -> &[Box<dyn Shape>] {
+        &self.children
+    }
+
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    pub fn add_child(&mut self, child: impl Shape + 'static) {
+        self.push_child(Box::new(child));
+    }
+
+    pub fn with_child(mut self, child: impl Shape + 'static) -> Self {
+        self.add_child(child);
+        self
+    }
+
+    fn push_child(&mut self, mut child: Box<dyn Shape>) {
+        child.set_parent_transform(self.world_transform());
+        self.bounds.merge(&child.parent_space_bounds());
+        self.children.push(child);
+    }
+
+    // Move the children fitting entirely in either half of the group's bounds into two lists, leaving
+    // the rest in place (both lists are empty if there is nothing to gain from splitting)
+    fn partition_children(&mut self) -> (Children, Children) {
+        // Children with infinite bounds (like planes) stay put, and must not stretch the halves
+        let mut finite = BoundingBox::empty();
+        for child in self.children.iter() {
+            let b = child.parent_space_bounds();
+            if b.is_finite() {
+                finite.merge(&b);
+            }
+        }
+
+        let (left_box, right_box) = finite.split();
+        let (mut left, mut right) = (Vec::new(), Vec::new());
+
+        for child in std::mem::take(&mut self.children) {
+            let b = child.parent_space_bounds();
+
+            if left_box.contains_box(&b) {
+                left.push(child);
+            } else if right_box.contains_box(&b) {
+                right.push(child);
+            } else {
+                self.children.push(child);
+            }
+        }
+
+        // A subgroup with every child would only be split the same way again
+        if self.children.is_empty() && (left.is_empty() || right.is_empty()) {
+            self.children.append(&mut left);
+            self.children.append(&mut right);
+        }
+
+        (left, right)
+    }
+
+    fn add_subgroup(&mut self, children: Children) {
+        let mut group = Group::new();
+        for child in children {
+            group.push_child(child);
+        }
+
+        self.push_child(Box::new(group));
+    }
+
+    // Transformation from the group's space to world space
+    fn world_transform(&self) -> Transform {
+        self.transform().then(self.parent_transform())
+    }
+
+    fn update_children(&mut self) {
+        let world = self.world_transform();
+
+        for child in self.children.iter_mut() {
+            child.set_parent_transform(world);
+        }
+    }
+}
+
+impl Shape for Group {
+    fn data(&self) -> &ShapeData {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut ShapeData {
+        &mut self.data
+    }
+
+    fn set_transform(&mut self, transform: Transform) {
+        self.data.transform = transform;
+        self.update_children();
+    }
+
+    fn set_parent_transform(&mut self, parent: Transform) {
+        self.data.parent = parent;
+        self.update_children();
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.bounds
+    }
+
+    fn includes(&self, other: &dyn Shape) -> bool {
+        ptr::addr_eq(self, other) || self.children.iter().any(|child| child.includes(other))
+    }
+
+    fn divide(&mut self, threshold: usize) {
+        if self.children.len() >= threshold {
+            let (left, right) = self.partition_children();
+
+            if !left.is_empty() {
+                self.add_subgroup(left);
+            }
+            if !right.is_empty() {
+                self.add_subgroup(right);
+            }
+        }
+
+        for child in self.children.iter_mut() {
+            child.divide(threshold);
+        }
+    }
+
+    fn local_intersect<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a>) {
+        if !self.bounds.intersects(ray) {
+            return;
+        }
+
+        for child in self.children.iter() {
+            child.intersect_into(ray, xs);
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Always, as a group has no surface of its own (intersections are with its children).
+    fn local_normal_at(&self, _point: &Point) -> Vector {
+        panic!("groups have no surface normal")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use crate::{
+        Angle, BoundingBox, Cone, Group, Intersections, Plane, Point, Ray, Shape, Sphere, Transform, Vector,
+    };
+
+    const EPSILON: f64 = 1.0e-4;
+
+    // A sphere nested two groups deep, `inner` scaling it and `outer` rotating by a quarter turn
+    fn nested(scaling: Transform) -> Group {
+        let inner = Group::new()
+            .with_transform(scaling)
+            .with_child(Sphere::new().with_transform(Transform::translation(5.0, 0.0, 0.0)));
+
+        // Transformed after the child is added, which must still reach the sphere
+        Group::new()
+            .with_child(inner)
+            .with_transform(Transform::rotation_y(Angle::radians(FRAC_PI_2)))
+    }
+
+    #[test]
+    fn new_group_is_empty() {
+        let g = Group::new();
+
+        assert!(g.is_empty());
+        assert_eq!(*g.transform(), Transform::identity());
+    }
+
+    #[test]
+    fn adding_a_child_sets_its_parent_transform() {
+        let g = Group::new()
+            .with_transform(Transform::translation(1.0, 0.0, 0.0))
+            .with_child(Sphere::new());
+
+        assert_eq!(g.len(), 1);
+        assert_eq!(*g.children()[0].parent_transform(), Transform::translation(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn bounds_contain_all_children() {
+        let sphere = Transform::scaling(2.0, 2.0, 2.0).then(&Transform::translation(2.0, 5.0, -3.0));
+        let cone = Transform::scaling(0.5, 1.0, 0.5).then(&Transform::translation(-4.0, -1.0, 4.0));
+        let g = Group::new()
+            .with_child(Sphere::new().with_transform(sphere))
+            .with_child(Cone::truncated(-2.0, 2.0, false).with_transform(cone));
+        let b = g.bounds();
+
+        assert_eq!(b.min, Point::new(-5.0, -3.0, -5.0));
+        assert_eq!(b.max, Point::new(4.0, 7.0, 5.0));
+        assert_eq!(g.parent_space_bounds(), b);
+    }
+
+    #[test]
+    fn intersecting_an_empty_group() {
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        let g = Group::new();
+        let mut xs = Intersections::new();
+        g.local_intersect(&r, &mut xs);
+
+        assert!(xs.is_empty());
+    }
+
+    #[test]
+    fn intersecting_a_nonempty_group() {
+        let g = Group::new()
+            .with_child(Sphere::new())
+            .with_child(Sphere::new().with_transform(Transform::translation(0.0, 0.0, -3.0)))
+            .with_child(Sphere::new().with_transform(Transform::translation(5.0, 0.0, 0.0)));
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut xs = Intersections::new();
+        g.local_intersect(&r, &mut xs);
+
+        let (s1, s2) = (&*g.children()[0], &*g.children()[1]);
+        assert_eq!(xs.len(), 4);
+        assert!(std::ptr::addr_eq(xs[0].object, s2));
+        assert!(std::ptr::addr_eq(xs[1].object, s2));
+        assert!(std::ptr::addr_eq(xs[2].object, s1));
+        assert!(std::ptr::addr_eq(xs[3].object, s1));
+    }
+
+    #[test]
+    fn intersecting_a_transformed_group() {
+        let g = Group::new()
+            .with_transform(Transform::scaling(2.0, 2.0, 2.0))
+            .with_child(Sphere::new().with_transform(Transform::translation(5.0, 0.0, 0.0)));
+        let r = Ray::new(Point::new(10.0, 0.0, -10.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert_eq!(g.intersect(&r).len(), 2);
+    }
+
+    #[test]
+    fn converting_a_point_from_world_to_object_space() {
+        let g = nested(Transform::scaling(2.0, 2.0, 2.0));
+        let r = Ray::new(Point::new(-5.0, 0.0, -10.0), Vector::new(1.0, 0.0, 0.0));
+        let xs = g.intersect(&r);
+        let hit = xs.hit().unwrap();
+
+        let p = hit.object.world_to_object(&r.position(hit.t));
+        assert!((p - Point::new(0.0, 0.0, -1.0)).length() < EPSILON);
+    }
+
+    #[test]
+    fn converting_a_normal_from_object_to_world_space() {
+        let g = nested(Transform::scaling(1.0, 2.0, 3.0));
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, -1.0));
+        let xs = g.intersect(&r);
+        let s = xs.hit().unwrap().object;
+        let expected = Vector::new(0.2857, 0.4286, -0.8571);
+
+        let k = 3f64.sqrt() / 3.0;
+        assert!((s.normal_to_world(&Vector::new(k, k, k)) - expected).length() < EPSILON);
+        assert!((s.normal_at(&Point::new(1.7321, 1.1547, -5.5774)) - expected).length() < EPSILON);
+    }
+
+    fn sphere_at(x: f64, y: f64, z: f64) -> Sphere {
+        Sphere::new().with_transform(Transform::translation(x, y, z))
+    }
+
+    fn unit_box_at(x: f64, y: f64, z: f64) -> BoundingBox {
+        BoundingBox::new(Point::new(x - 1.0, y - 1.0, z - 1.0), Point::new(x + 1.0, y + 1.0, z + 1.0))
+    }
+
+    #[test]
+    fn partitioning_children_by_bounds() {
+        let mut g = Group::new()
+            .with_child(sphere_at(-2.0, 0.0, 0.0))
+            .with_child(sphere_at(2.0, 0.0, 0.0))
+            .with_child(Sphere::new());
+        let (left, right) = g.partition_children();
+
+        assert_eq!(g.len(), 1);
+        assert_eq!(g.children()[0].parent_space_bounds(), unit_box_at(0.0, 0.0, 0.0));
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].parent_space_bounds(), unit_box_at(-2.0, 0.0, 0.0));
+        assert_eq!(right.len(), 1);
+        assert_eq!(right[0].parent_space_bounds(), unit_box_at(2.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn dividing_creates_subgroups() {
+        let mut g = Group::new()
+            .with_child(sphere_at(-2.0, -2.0, 0.0))
+            .with_child(sphere_at(-2.0, 2.0, 0.0))
+            .with_child(Sphere::new().with_transform(Transform::scaling(4.0, 4.0, 4.0)));
+        g.divide(1);
+
+        assert_eq!(g.len(), 2);
+        assert_eq!(g.children()[0].parent_space_bounds().max, Point::new(4.0, 4.0, 4.0));
+
+        let sub = &g.children()[1];
+        let expected = BoundingBox::new(Point::new(-3.0, -3.0, -1.0), Point::new(-1.0, 3.0, 1.0));
+        assert_eq!(sub.bounds(), expected);
+    }
+
+    #[test]
+    fn groups_below_the_threshold_are_not_divided() {
+        let mut g = Group::new()
+            .with_child(sphere_at(-2.0, 0.0, 0.0))
+            .with_child(sphere_at(2.0, 1.0, -5.0))
+            .with_child(sphere_at(2.0, -1.0, 5.0));
+        g.divide(4);
+
+        assert_eq!(g.len(), 3);
+    }
+
+    #[test]
+    fn coincident_children_are_not_divided() {
+        let mut g = Group::new()
+            .with_child(Sphere::new())
+            .with_child(Sphere::new())
+            .with_child(Sphere::new());
+        g.divide(1);
+
+        assert_eq!(g.len(), 3);
+    }
+
+    #[test]
+    fn dividing_keeps_the_same_intersections() {
+        let mut g = Group::new()
+            .with_transform(Transform::rotation_y(Angle::radians(0.3)))
+            .with_child(Plane::new().with_transform(Transform::translation(0.0, -2.0, 0.0)));
+        for i in 0..5 {
+            for j in 0..5 {
+                g.add_child(sphere_at(3.0 * i as f64, 0.0, 3.0 * j as f64));
+            }
+        }
+        let rays = [
+            Ray::new(Point::new(-5.0, 0.0, 0.1), Vector::new(1.0, 0.0, 0.0)),
+            Ray::new(Point::new(3.0, 10.0, 6.0), Vector::new(0.1, -1.0, 0.2)),
+            Ray::new(Point::new(-5.0, 5.0, -5.0), Vector::new(1.0, -0.5, 1.0)),
+        ];
+        let hits = |g: &Group| -> Vec<Vec<f64>> {
+            rays.iter().map(|r| g.intersect(r).iter().map(|i| i.t).collect()).collect()
+        };
+
+        let before = hits(&g);
+        g.divide(4);
+
+        assert!(g.len() < 26);
+        assert_eq!(hits(&g), before);
+    }
+
+    #[test]
+    fn rays_missing_the_bounds_skip_the_children() {
+        let g = Group::new().with_child(sphere_at(5.0, 0.0, 0.0));
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert!(!g.bounds().intersects(&r));
+        assert!(g.intersect(&r).is_empty());
+    }
+}
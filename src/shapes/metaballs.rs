@@ -0,0 +1,274 @@
+use crate::{BoundingBox, Intersection, Intersections, Point, Ray, Shape, ShapeData, Vector};
+
+use super::cube::intersect_box;
+
+// Ray marching steps per radius of the smallest ball
+const STEPS_PER_RADIUS: f64 = 16.0;
+
+// Most ray marching steps through the bounds, coarsening the step for balls tiny next to the field
+const MAX_STEPS: f64 = 4096.0;
+
+// Bisection iterations refining each surface crossing
+const REFINEMENTS: usize = 48;
+
+/// Weighted center of a [`Metaballs`] field
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Metaball {
+    pub center: Point,
+    /// Distance at which the ball stops contributing to the field
+    pub radius: f64,
+    /// Field strength at the center
+    pub weight: f64,
+}
+
+impl Metaball {
+    // Contribution to the field at `point`, falling smoothly to zero at the radius
+    fn field(&self, point: &Point) -> f64 {
+        let d = *point - self.center;
+        let s = d.dot(&d) / (self.radius * self.radius);
+
+        if s < 1.0 {
+            self.weight * (1.0 - s).powi(3)
+        } else {
+            0.0
+        }
+    }
+
+    // Gradient of the contribution at `point`
+    fn gradient(&self, point: &Point) -> Vector {
+        let d = *point - self.center;
+        let r2 = self.radius * self.radius;
+        let s = d.dot(&d) / r2;
+
+        if s < 1.0 {
+            d * (-6.0 * self.weight * (1.0 - s).powi(2) / r2)
+        } else {
+            Vector::zero()
+        }
+    }
+}
+
+/// Blobby isosurface around a set of weighted centers
+///
+/// Each ball contributes a field falling off to zero at its radius, and the surface is where the
+/// summed field reaches `threshold`, so nearby balls melt into each other. Intersections are found
+/// by ray marching, in steps of a sixteenth of the smallest radius (but at most 4096 steps across
+/// the bounds), so features thinner than a step can be missed.
+///
+/// ```
+/// use ray_tracer_challenge::{Metaballs, Point, Ray, Shape, Vector};
+///
+/// // Each ball alone ends short of the origin, but together they bridge the gap
+/// let blob = Metaballs::new(0.5)
+///     .with_ball(Point::new(-1.0, 0.0, 0.0), 2.0, 1.0)
+///     .with_ball(Point::new(1.0, 0.0, 0.0), 2.0, 1.0);
+///
+/// let xs = blob.intersect(&Ray::new(Point::new(0.0, -5.0, 0.0), Vector::new(0.0, 1.0, 0.0)));
+/// assert_eq!(xs.len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Metaballs {
+    data: ShapeData,
+    balls: Vec<Metaball>,
+    threshold: f64,
+    bounds: BoundingBox,
+}
+
+impl Metaballs {
+    /// Empty field, with the surface where it reaches `threshold`
+    ///
+    /// # Panics
+    ///
+    /// If `threshold` is not positive, as the surface would not be bounded.
+    pub fn new(threshold: f64) -> Self {
+        assert!(threshold > 0.0, "metaball threshold must be positive");
+
+        Self {
+            data: Default::default(),
+            balls: Vec::new(),
+            threshold,
+            bounds: BoundingBox::empty(),
+        }
+    }
+
+    pub fn balls(&self) -> &[Metaball] {
+        &self.balls
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Add a ball of `radius` around `center`, with a field strength of `weight` at its center
+    ///
+    /// # Panics
+    ///
+    /// If `radius` is not positive and finite.
+    pub fn add_ball(&mut self, center: Point, radius: f64, weight: f64) {
+        assert!(radius > 0.0 && radius.is_finite(), "metaball radius must be positive and finite");
+
+        let extent = Vector::new(radius, radius, radius);
+        self.bounds.merge(&BoundingBox::new(center - extent, center + extent));
+        self.balls.push(Metaball { center, radius, weight });
+    }
+
+    /// Builder form of [`add_ball`](Self::add_ball)
+    pub fn with_ball(mut self, center: Point, radius: f64, weight: f64) -> Self {
+        self.add_ball(center, radius, weight);
+        self
+    }
+
+    /// Summed field of all balls at `point`
+    pub fn field(&self, point: &Point) -> f64 {
+        self.balls.iter().map(|b| b.field(point)).sum()
+    }
+
+    // Field relative to the threshold, positive inside the surface
+    fn level(&self, ray: &Ray, t: f64) -> f64 {
+        self.field(&ray.position(t)) - self.threshold
+    }
+
+    // Bisect a crossing of the surface between `lo` and `hi`
+    fn refine(&self, ray: &Ray, mut lo: f64, mut hi: f64, lo_inside: bool) -> f64 {
+        for _ in 0..REFINEMENTS {
+            let mid = 0.5 * (lo + hi);
+            if (self.level(ray, mid) > 0.0) == lo_inside {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+
+        0.5 * (lo + hi)
+    }
+}
+
+impl Shape for Metaballs {
+    fn data(&self) -> &ShapeData {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut ShapeData {
+        &mut self.data
+    }
+
+    fn local_intersect<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a>) {
+        if self.balls.is_empty() {
+            return;
+        }
+
+        let (tmin, tmax) = match intersect_box(ray, &self.bounds.min, &self.bounds.max) {
+            Some(range) => range,
+            None => return,
+        };
+
+        let min_radius = self.balls.iter().map(|b| b.radius).fold(f64::INFINITY, f64::min);
+        let step = min_radius / STEPS_PER_RADIUS / ray.direction.length();
+        let step = step.max((tmax - tmin) / MAX_STEPS);
+
+        let mut t0 = tmin;
+        let mut inside = self.level(ray, t0) > 0.0;
+        while t0 < tmax {
+            let t1 = (t0 + step).min(tmax);
+            let now_inside = self.level(ray, t1) > 0.0;
+
+            if now_inside != inside {
+                xs.push(Intersection::new(self.refine(ray, t0, t1, inside), self));
+                inside = now_inside;
+            }
+
+            t0 = t1;
+        }
+    }
+
+    fn local_normal_at(&self, point: &Point) -> Vector {
+        // The field grows towards the centers, so the outward normal is against the gradient
+        -self.balls.iter().fold(Vector::zero(), |acc, b| acc + b.gradient(point))
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.bounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Metaballs, Point, Ray, Shape, Vector};
+
+    const EPSILON: f64 = 1.0e-9;
+
+    fn pair() -> Metaballs {
+        Metaballs::new(0.5)
+            .with_ball(Point::new(-1.0, 0.0, 0.0), 2.0, 1.0)
+            .with_ball(Point::new(1.0, 0.0, 0.0), 2.0, 1.0)
+    }
+
+    #[test]
+    fn single_ball_is_a_sphere() {
+        let m = Metaballs::new(0.5).with_ball(Point::zero(), 2.0, 1.0);
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = m.intersect(&r);
+
+        // (1 - d²/R²)³ = threshold
+        let d = 2.0 * (1.0 - 0.5f64.cbrt()).sqrt();
+        assert_eq!(xs.len(), 2);
+        assert!((xs[0].t - (5.0 - d)).abs() < EPSILON);
+        assert!((xs[1].t - (5.0 + d)).abs() < EPSILON);
+    }
+
+    #[test]
+    fn nearby_balls_merge() {
+        let r = Ray::new(Point::new(0.0, -5.0, 0.0), Vector::new(0.0, 1.0, 0.0));
+        let alone = Metaballs::new(0.5).with_ball(Point::new(1.0, 0.0, 0.0), 2.0, 1.0);
+
+        assert!(alone.intersect(&r).is_empty());
+        assert_eq!(pair().intersect(&r).len(), 2);
+        assert!(pair().field(&Point::zero()) > 0.5);
+    }
+
+    #[test]
+    fn rays_miss_outside_the_bounds() {
+        let r = Ray::new(Point::new(0.0, 5.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert!(pair().intersect(&r).is_empty());
+        assert!(Metaballs::new(1.0).intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn normals_point_away_from_the_centers() {
+        let m = pair();
+
+        assert_eq!(m.normal_at(&Point::new(0.0, 0.5, 0.0)), Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(m.normal_at(&Point::new(2.5, 0.0, 0.0)), Vector::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn marching_ends_for_balls_tiny_next_to_the_field() {
+        let m = Metaballs::new(0.5)
+            .with_ball(Point::zero(), 1.0e6, 1.0)
+            .with_ball(Point::new(10.0, 0.0, 0.0), 1.0e-9, 1.0);
+        let r = Ray::new(Point::new(0.0, 0.0, -2.0e6), Vector::new(0.0, 0.0, 1.0));
+
+        assert_eq!(m.intersect(&r).len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_radius_panics() {
+        Metaballs::new(0.5).with_ball(Point::zero(), 0.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_radius_panics() {
+        Metaballs::new(0.5).with_ball(Point::zero(), -1.0, 1.0);
+    }
+
+    #[test]
+    fn bounds_enclose_every_ball() {
+        let b = pair().bounds();
+
+        assert_eq!(b.min, Point::new(-3.0, -2.0, -2.0));
+        assert_eq!(b.max, Point::new(3.0, 2.0, 2.0));
+    }
+}
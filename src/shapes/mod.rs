@@ -0,0 +1,273 @@
+//! Renderable shapes
+//!
+//! Every shape implements [`Shape`], only having to deal with rays and points in its own object
+//! space: the conversion to and from world space is done once, by the trait's provided methods.
+
+use std::fmt::Debug;
+use std::ptr;
+
+use crate::{Intersection, Intersections, Material, Point, Ray, Transform, Vector};
+
+mod bounds;
+mod caps;
+mod cone;
+mod csg;
+mod cube;
+mod group;
+mod metaballs;
+mod plane;
+mod sphere;
+mod triangle;
+mod voxel_grid;
+
+pub use bounds::*;
+pub use cone::*;
+pub use csg::*;
+pub use cube::*;
+pub use group::*;
+pub use metaballs::*;
+pub use plane::*;
+pub use sphere::*;
+pub use triangle::*;
+pub use voxel_grid::*;
+
+// Tolerance for treating a ray as parallel to a surface
+const EPSILON: f64 = 1.0e-8;
+
+/// State common to all shapes
+#[derive(Clone, Default, Debug)]
+pub struct ShapeData {
+    transform: Transform,
+    // Combined transformation of the groups containing the shape, outermost applied last
+    parent: Transform,
+    pub material: Material,
+}
+
+/// A renderable object
+pub trait Shape: Debug {
+    /// Common shape state
+    fn data(&self) -> &ShapeData;
+
+    /// Common shape state
+    fn data_mut(&mut self) -> &mut ShapeData;
+
+    /// Add the intersections of an object space ray to `xs`
+    fn local_intersect<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a>);
+
+    /// Surface normal at an object space point
+    fn local_normal_at(&self, point: &Point) -> Vector;
+
+    /// Bounding box in object space
+    fn bounds(&self) -> BoundingBox;
+
+    /// Surface normal at an object space point of the intersection `hit`
+    ///
+    /// Only needs overriding by shapes whose normal depends on more than the point, such as the
+    /// interpolated normals of a [`SmoothTriangle`].
+    fn local_normal_at_hit(&self, point: &Point, _hit: &Intersection) -> Vector {
+        self.local_normal_at(point)
+    }
+
+    fn transform(&self) -> &Transform {
+        &self.data().transform
+    }
+
+    fn set_transform(&mut self, transform: Transform) {
+        self.data_mut().transform = transform;
+    }
+
+    /// Combined transformation of the [`Group`]s containing the shape
+    fn parent_transform(&self) -> &Transform {
+        &self.data().parent
+    }
+
+    /// Set the combined transformation of the containing groups
+    ///
+    /// Kept up to date by [`Group`] as shapes are added to it and as it is transformed, so there
+    /// should be no need to call this directly.
+    fn set_parent_transform(&mut self, parent: Transform) {
+        self.data_mut().parent = parent;
+    }
+
+    fn with_transform(mut self, transform: Transform) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_transform(transform);
+        self
+    }
+
+    fn material(&self) -> &Material {
+        &self.data().material
+    }
+
+    fn set_material(&mut self, material: Material) {
+        self.data_mut().material = material;
+    }
+
+    fn with_material(mut self, material: Material) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_material(material);
+        self
+    }
+
+    /// Material at a world space point on the surface
+    ///
+    /// The shape's material, unless overridden by shapes whose surface varies (like the colored
+    /// voxels of a [`VoxelGrid`]).
+    fn material_at(&self, _point: &Point) -> Material {
+        *self.material()
+    }
+
+    /// Bounding box in the space of the containing group (or world space, if there is none)
+    fn parent_space_bounds(&self) -> BoundingBox {
+        self.bounds().transform(self.transform())
+    }
+
+    /// Reorganize composite shapes into a bounding volume hierarchy
+    ///
+    /// Groups with at least `threshold` children have them split into subgroups by bounding box,
+    /// recursively, so that rays missing a subgroup's box skip all of its children. Does nothing for
+    /// primitive shapes.
+    fn divide(&mut self, _threshold: usize) {}
+
+    /// Whether `other` is this shape or, for composite shapes, one of its descendants
+    fn includes(&self, other: &dyn Shape) -> bool {
+        ptr::addr_eq(self, other)
+    }
+
+    /// Add the intersections of a world space ray to `xs`
+    fn intersect_into<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a>) {
+        self.local_intersect(&ray.transform(self.transform().inverse()), xs);
+    }
+
+    /// Intersections of a world space ray
+    fn intersect(&self, ray: &Ray) -> Intersections<'_> {
+        let mut xs = Intersections::new();
+        self.intersect_into(ray, &mut xs);
+        xs
+    }
+
+    /// Convert a world space point to object space, through any containing groups
+    fn world_to_object(&self, point: &Point) -> Point {
+        self.transform().inverse() * (self.parent_transform().inverse() * point)
+    }
+
+    /// Convert an object space normal to a normalized world space one, through any containing groups
+    fn normal_to_world(&self, normal: &Vector) -> Vector {
+        let normal = self.transform().inverse().transposed() * normal;
+
+        (self.parent_transform().inverse().transposed() * normal).normalize()
+    }
+
+    /// Surface normal at a world space point
+    fn normal_at(&self, point: &Point) -> Vector {
+        self.normal_to_world(&self.local_normal_at(&self.world_to_object(point)))
+    }
+
+    /// Surface normal at a world space point of the intersection `hit`
+    fn normal_at_hit(&self, point: &Point, hit: &Intersection) -> Vector {
+        self.normal_to_world(&self.local_normal_at_hit(&self.world_to_object(point), hit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    use crate::{BoundingBox, Intersections, Material, Point, Ray, Shape, ShapeData, Sphere, Transform, Vector};
+
+    // Shape recording the object space ray it is intersected with
+    #[derive(Default, Debug)]
+    struct TestShape {
+        data: ShapeData,
+        saved_ray: RefCell<Option<Ray>>,
+    }
+
+    impl Shape for TestShape {
+        fn data(&self) -> &ShapeData {
+            &self.data
+        }
+
+        fn data_mut(&mut self) -> &mut ShapeData {
+            &mut self.data
+        }
+
+        fn local_intersect<'a>(&'a self, ray: &Ray, _xs: &mut Intersections<'a>) {
+            *self.saved_ray.borrow_mut() = Some(*ray);
+        }
+
+        fn local_normal_at(&self, point: &Point) -> Vector {
+            Vector::from(*point)
+        }
+
+        fn bounds(&self) -> BoundingBox {
+            BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+        }
+    }
+
+    #[test]
+    fn default_transform_and_material() {
+        let s = TestShape::default();
+
+        assert_eq!(*s.transform(), Transform::identity());
+        assert_eq!(*s.material(), Material::default());
+    }
+
+    #[test]
+    fn assigning_transform_and_material() {
+        let m = Material { ambient: 1.0, ..Default::default() };
+        let s = TestShape::default()
+            .with_transform(Transform::translation(2.0, 3.0, 4.0))
+            .with_material(m);
+
+        assert_eq!(*s.transform(), Transform::translation(2.0, 3.0, 4.0));
+        assert_eq!(*s.material(), m);
+    }
+
+    #[test]
+    fn intersecting_converts_the_ray_to_object_space() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        let s = TestShape::default().with_transform(Transform::scaling(2.0, 2.0, 2.0));
+        s.intersect(&r);
+        let saved = s.saved_ray.borrow().unwrap();
+        assert_eq!(saved.origin, Point::new(0.0, 0.0, -2.5));
+        assert_eq!(saved.direction, Vector::new(0.0, 0.0, 0.5));
+
+        let s = TestShape::default().with_transform(Transform::translation(5.0, 0.0, 0.0));
+        s.intersect(&r);
+        let saved = s.saved_ray.borrow().unwrap();
+        assert_eq!(saved.origin, Point::new(-5.0, 0.0, -5.0));
+        assert_eq!(saved.direction, Vector::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn normals_are_converted_to_world_space() {
+        let s = TestShape::default().with_transform(Transform::translation(0.0, 1.0, 0.0));
+        let n = s.normal_at(&Point::new(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+        assert!((n - Vector::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2)).length() < 1e-12);
+    }
+
+    #[test]
+    fn shapes_share_one_collection() {
+        let shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(Sphere::new()),
+            Box::new(TestShape::default()),
+            Box::new(Sphere::new().with_transform(Transform::translation(0.0, 0.0, 10.0))),
+        ];
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        let mut xs = Intersections::new();
+        for s in shapes.iter() {
+            s.intersect_into(&r, &mut xs);
+        }
+
+        let ts: Vec<f64> = xs.iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![4.0, 6.0, 14.0, 16.0]);
+        assert!(std::ptr::addr_eq(xs[2].object, &*shapes[2]));
+    }
+}
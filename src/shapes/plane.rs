@@ -0,0 +1,94 @@
+use crate::{BoundingBox, Intersection, Intersections, Point, Ray, Shape, ShapeData, Vector};
+
+use super::EPSILON;
+
+/// Infinite plane spanning the x and z axes
+#[derive(Clone, Default, Debug)]
+pub struct Plane {
+    data: ShapeData,
+}
+
+impl Plane {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Shape for Plane {
+    fn data(&self) -> &ShapeData {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut ShapeData {
+        &mut self.data
+    }
+
+    fn local_intersect<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a>) {
+        // Parallel (or coplanar) rays never hit
+        if ray.direction.y.abs() < EPSILON {
+            return;
+        }
+
+        let t = -ray.origin.y / ray.direction.y;
+        xs.push(Intersection::new(t, self));
+    }
+
+    fn local_normal_at(&self, _point: &Point) -> Vector {
+        Vector::new(0.0, 1.0, 0.0)
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Point::new(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Point::new(f64::INFINITY, 0.0, f64::INFINITY),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Intersections, Plane, Point, Ray, Shape, Vector};
+
+    #[test]
+    fn normal_is_constant_everywhere() {
+        let p = Plane::new();
+        let n = Vector::new(0.0, 1.0, 0.0);
+
+        assert_eq!(p.local_normal_at(&Point::new(0.0, 0.0, 0.0)), n);
+        assert_eq!(p.local_normal_at(&Point::new(10.0, 0.0, -10.0)), n);
+        assert_eq!(p.local_normal_at(&Point::new(-5.0, 0.0, 150.0)), n);
+    }
+
+    #[test]
+    fn bounds_are_infinite_but_flat() {
+        let b = Plane::new().bounds();
+
+        assert_eq!(b.min, Point::new(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY));
+        assert_eq!(b.max, Point::new(f64::INFINITY, 0.0, f64::INFINITY));
+    }
+
+    #[test]
+    fn parallel_and_coplanar_rays_miss() {
+        let p = Plane::new();
+        let mut xs = Intersections::new();
+
+        p.local_intersect(&Ray::new(Point::new(0.0, 10.0, 0.0), Vector::new(0.0, 0.0, 1.0)), &mut xs);
+        p.local_intersect(&Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0)), &mut xs);
+
+        assert!(xs.is_empty());
+    }
+
+    #[test]
+    fn rays_from_above_and_below() {
+        let p = Plane::new();
+
+        let xs = p.intersect(&Ray::new(Point::new(0.0, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0)));
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 1.0);
+        assert!(std::ptr::addr_eq(xs[0].object, &p));
+
+        let xs = p.intersect(&Ray::new(Point::new(0.0, -1.0, 0.0), Vector::new(0.0, 1.0, 0.0)));
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 1.0);
+    }
+}
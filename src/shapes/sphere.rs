@@ -0,0 +1,165 @@
+use crate::{
+    solve_reduced_quadratic, BoundingBox, Intersection, Intersections, Point, Ray, Shape, ShapeData, Vector,
+};
+
+/// Unit sphere centered at the origin
+#[derive(Clone, Default, Debug)]
+pub struct Sphere {
+    data: ShapeData,
+}
+
+impl Sphere {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Shape for Sphere {
+    fn data(&self) -> &ShapeData {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut ShapeData {
+        &mut self.data
+    }
+
+    fn local_intersect<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a>) {
+        let d = ray.direction;
+        let f = Vector::from(ray.origin);
+
+        let a = d.dot(&d);
+        let half_b = d.dot(&f);
+        let c = f.dot(&f) - 1.0;
+
+        // Discriminant from the distance between the center and the ray, which unlike
+        // `half_b² - ac` does not cancel catastrophically for distant rays
+        let l = f - d * (half_b / a);
+        let disc = a * (1.0 - l.dot(&l));
+
+        if let Some((t0, t1)) = solve_reduced_quadratic(a, half_b, c, disc) {
+            xs.push(Intersection::new(t0, self));
+            xs.push(Intersection::new(t1, self));
+        }
+    }
+
+    fn local_normal_at(&self, point: &Point) -> Vector {
+        Vector::from(*point)
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+    use crate::{Angle, Point, Ray, Shape, Sphere, Transform, Vector};
+
+    const EPSILON: f64 = 1.0e-12;
+
+    fn ts(s: &Sphere, r: &Ray) -> Vec<f64> {
+        s.intersect(r).iter().map(|i| i.t).collect()
+    }
+
+    #[test]
+    fn ray_intersects_sphere_at_two_points() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert_eq!(ts(&Sphere::new(), &r), vec![4.0, 6.0]);
+    }
+
+    #[test]
+    fn ray_intersects_sphere_at_a_tangent() {
+        let r = Ray::new(Point::new(0.0, 1.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert_eq!(ts(&Sphere::new(), &r), vec![5.0, 5.0]);
+    }
+
+    #[test]
+    fn ray_misses_sphere() {
+        let r = Ray::new(Point::new(0.0, 2.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert!(Sphere::new().intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn ray_originates_inside_sphere() {
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert_eq!(ts(&Sphere::new(), &r), vec![-1.0, 1.0]);
+    }
+
+    #[test]
+    fn sphere_is_behind_ray() {
+        let r = Ray::new(Point::new(0.0, 0.0, 5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert_eq!(ts(&Sphere::new(), &r), vec![-6.0, -4.0]);
+    }
+
+    #[test]
+    fn intersect_sets_the_object() {
+        let s = Sphere::new();
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = s.intersect(&r);
+
+        assert!(xs.iter().all(|i| std::ptr::addr_eq(i.object, &s)));
+    }
+
+    #[test]
+    fn intersecting_transformed_spheres() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        let s = Sphere::new().with_transform(Transform::scaling(2.0, 2.0, 2.0));
+        assert_eq!(ts(&s, &r), vec![3.0, 7.0]);
+
+        let s = Sphere::new().with_transform(Transform::translation(5.0, 0.0, 0.0));
+        assert!(s.intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn tiny_distant_sphere_is_hit_precisely() {
+        let s = Sphere::new().with_transform(Transform::scaling(1e-4, 1e-4, 1e-4));
+        let r = Ray::new(Point::new(0.0, 0.0, -1e6), Vector::new(0.0, 0.0, 1.0));
+        let xs = s.intersect(&r);
+
+        assert_eq!(xs.len(), 2);
+        assert!((xs[0].t - (1e6 - 1e-4)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn bounds_are_the_unit_cube() {
+        let b = Sphere::new().bounds();
+
+        assert_eq!(b.min, Point::new(-1.0, -1.0, -1.0));
+        assert_eq!(b.max, Point::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn normals_on_the_axes_and_off_axis() {
+        let s = Sphere::new();
+
+        assert_eq!(s.normal_at(&Point::new(1.0, 0.0, 0.0)), Vector::new(1.0, 0.0, 0.0));
+        assert_eq!(s.normal_at(&Point::new(0.0, 1.0, 0.0)), Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(s.normal_at(&Point::new(0.0, 0.0, 1.0)), Vector::new(0.0, 0.0, 1.0));
+
+        let k = 3f64.sqrt() / 3.0;
+        let n = s.normal_at(&Point::new(k, k, k));
+        assert!((n - Vector::new(k, k, k)).length() < EPSILON);
+        assert!((n - n.normalize()).length() < EPSILON);
+    }
+
+    #[test]
+    fn normals_on_transformed_spheres() {
+        let s = Sphere::new().with_transform(Transform::translation(0.0, 1.0, 0.0));
+        let n = s.normal_at(&Point::new(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+        assert!((n - Vector::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2)).length() < EPSILON);
+
+        let t = Transform::rotation_z(Angle::radians(PI / 5.0)).then(&Transform::scaling(1.0, 0.5, 1.0));
+        let s = Sphere::new().with_transform(t);
+        let h = 2f64.sqrt() / 2.0;
+        let n = s.normal_at(&Point::new(0.0, h, -h));
+        assert!((n - Vector::new(0.0, 0.97014, -0.24254)).length() < 1e-5);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{BoundingBox, Intersection, Intersections, Point, Ray, Shape, ShapeData, Vector};
+
+use super::EPSILON;
+
+/// Flat triangle between three points
+#[derive(Clone, Debug)]
+pub struct Triangle {
+    data: ShapeData,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    e1: Vector,
+    e2: Vector,
+    normal: Vector,
+}
+
+impl Triangle {
+    pub fn new(p1: Point, p2: Point, p3: Point) -> Self {
+        let e1 = p2 - p1;
+        let e2 = p3 - p1;
+        let normal = e2.cross(&e1).normalize();
+
+        Self { data: Default::default(), p1, p2, p3, e1, e2, normal }
+    }
+
+    pub fn p1(&self) -> &Point {
+        &self.p1
+    }
+
+    pub fn p2(&self) -> &Point {
+        &self.p2
+    }
+
+    pub fn p3(&self) -> &Point {
+        &self.p3
+    }
+
+    /// Face normal, from the winding order of the points
+    pub fn normal(&self) -> &Vector {
+        &self.normal
+    }
+
+    // Distance and barycentric coordinates of the hit (Möller–Trumbore), if any
+    fn intersect_uv(&self, ray: &Ray) -> Option<(f64, f64, f64)> {
+        let dir_cross_e2 = ray.direction.cross(&self.e2);
+        let det = self.e1.dot(&dir_cross_e2);
+
+        // Rays parallel to the triangle's plane miss
+        if det.abs() < EPSILON {
+            return None;
+        }
+
+        let f = 1.0 / det;
+        let p1_to_origin = ray.origin - self.p1;
+        let u = f * p1_to_origin.dot(&dir_cross_e2);
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let origin_cross_e1 = p1_to_origin.cross(&self.e1);
+        let v = f * ray.direction.dot(&origin_cross_e1);
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        Some((f * self.e2.dot(&origin_cross_e1), u, v))
+    }
+}
+
+impl Shape for Triangle {
+    fn data(&self) -> &ShapeData {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut ShapeData {
+        &mut self.data
+    }
+
+    fn local_intersect<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a>) {
+        if let Some((t, u, v)) = self.intersect_uv(ray) {
+            xs.push(Intersection::with_uv(t, self, u, v));
+        }
+    }
+
+    fn local_normal_at(&self, _point: &Point) -> Vector {
+        self.normal
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut res = BoundingBox::empty();
+        res.add_point(&self.p1);
+        res.add_point(&self.p2);
+        res.add_point(&self.p3);
+        res
+    }
+}
+
+/// Triangle with a normal at each point, interpolated across its surface
+///
+/// Hides the facets of meshes approximating curved surfaces. The interpolation needs the
+/// barycentric coordinates of the hit, so shading should use
+/// [`normal_at_hit`](Shape::normal_at_hit); the plain `normal_at` gives the flat face normal.
+#[derive(Clone, Debug)]
+pub struct SmoothTriangle {
+    triangle: Triangle,
+    n1: Vector,
+    n2: Vector,
+    n3: Vector,
+}
+
+impl SmoothTriangle {
+    pub fn new(p1: Point, p2: Point, p3: Point, n1: Vector, n2: Vector, n3: Vector) -> Self {
+        Self { triangle: Triangle::new(p1, p2, p3), n1, n2, n3 }
+    }
+
+    /// The flat triangle
+    pub fn triangle(&self) -> &Triangle {
+        &self.triangle
+    }
+
+    pub fn n1(&self) -> &Vector {
+        &self.n1
+    }
+
+    pub fn n2(&self) -> &Vector {
+        &self.n2
+    }
+
+    pub fn n3(&self) -> &Vector {
+        &self.n3
+    }
+}
+
+impl Shape for SmoothTriangle {
+    fn data(&self) -> &ShapeData {
+        &self.triangle.data
+    }
+
+    fn data_mut(&mut self) -> &mut ShapeData {
+        &mut self.triangle.data
+    }
+
+    fn local_intersect<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a>) {
+        if let Some((t, u, v)) = self.triangle.intersect_uv(ray) {
+            xs.push(Intersection::with_uv(t, self, u, v));
+        }
+    }
+
+    fn local_normal_at(&self, _point: &Point) -> Vector {
+        self.triangle.normal
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.triangle.bounds()
+    }
+
+    fn local_normal_at_hit(&self, _point: &Point, hit: &Intersection) -> Vector {
+        self.n2 * hit.u + self.n3 * hit.v + self.n1 * (1.0 - hit.u - hit.v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Intersection, Intersections, Point, Ray, Shape, SmoothTriangle, Triangle, Vector};
+
+    const EPSILON: f64 = 1.0e-5;
+
+    fn triangle() -> Triangle {
+        Triangle::new(Point::new(0.0, 1.0, 0.0), Point::new(-1.0, 0.0, 0.0), Point::new(1.0, 0.0, 0.0))
+    }
+
+    fn smooth_triangle() -> SmoothTriangle {
+        SmoothTriangle::new(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+            Vector::new(-1.0, 0.0, 0.0),
+            Vector::new(1.0, 0.0, 0.0),
+        )
+    }
+
+    fn local_intersect<S: Shape>(s: &S, origin: Point, direction: Vector) -> Intersections<'_> {
+        let mut xs = Intersections::new();
+        s.local_intersect(&Ray::new(origin, direction), &mut xs);
+        xs
+    }
+
+    #[test]
+    fn constructing_a_triangle() {
+        let t = triangle();
+
+        assert_eq!(t.e1, Vector::new(-1.0, -1.0, 0.0));
+        assert_eq!(t.e2, Vector::new(1.0, -1.0, 0.0));
+        assert_eq!(*t.normal(), Vector::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn bounds_enclose_the_points() {
+        let (p1, p2, p3) = (Point::new(-3.0, 7.0, 2.0), Point::new(6.0, 2.0, -4.0), Point::new(2.0, -1.0, -1.0));
+        let b = Triangle::new(p1, p2, p3).bounds();
+
+        assert_eq!(b.min, Point::new(-3.0, -1.0, -4.0));
+        assert_eq!(b.max, Point::new(6.0, 7.0, 2.0));
+    }
+
+    #[test]
+    fn normal_is_the_face_normal_everywhere() {
+        let t = triangle();
+
+        assert_eq!(t.local_normal_at(&Point::new(0.0, 0.5, 0.0)), *t.normal());
+        assert_eq!(t.local_normal_at(&Point::new(-0.5, 0.75, 0.0)), *t.normal());
+        assert_eq!(t.local_normal_at(&Point::new(0.5, 0.25, 0.0)), *t.normal());
+    }
+
+    #[test]
+    fn rays_missing_the_triangle() {
+        let t = triangle();
+        let cases = [
+            (Point::new(0.0, -1.0, -2.0), Vector::new(0.0, 1.0, 0.0)),
+            (Point::new(1.0, 1.0, -2.0), Vector::new(0.0, 0.0, 1.0)),
+            (Point::new(-1.0, 1.0, -2.0), Vector::new(0.0, 0.0, 1.0)),
+            (Point::new(0.0, -1.0, -2.0), Vector::new(0.0, 0.0, 1.0)),
+        ];
+
+        for &(origin, direction) in cases.iter() {
+            assert!(local_intersect(&t, origin, direction).is_empty());
+        }
+    }
+
+    #[test]
+    fn ray_strikes_the_triangle() {
+        let t = triangle();
+        let xs = local_intersect(&t, Point::new(0.0, 0.5, -2.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 2.0);
+    }
+
+    #[test]
+    fn intersection_with_smooth_triangle_stores_uv() {
+        let t = smooth_triangle();
+        let xs = local_intersect(&t, Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert_eq!(xs.len(), 1);
+        assert!((xs[0].u - 0.45).abs() < EPSILON);
+        assert!((xs[0].v - 0.25).abs() < EPSILON);
+        assert!(std::ptr::addr_eq(xs[0].object, &t));
+    }
+
+    #[test]
+    fn smooth_triangle_interpolates_the_normal() {
+        let t = smooth_triangle();
+        let hit = Intersection::with_uv(1.0, &t, 0.45, 0.25);
+        let n = t.normal_at_hit(&Point::new(0.0, 0.0, 0.0), &hit);
+
+        assert!((n - Vector::new(-0.5547, 0.83205, 0.0)).length() < EPSILON);
+    }
+}
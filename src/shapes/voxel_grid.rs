@@ -0,0 +1,308 @@
+use crate::{
+    BoundingBox, Color, Intersection, Intersections, Material, Point, Ray, Shape, ShapeData, Vector,
+};
+
+use super::cube::intersect_box;
+
+/// Dense grid of unit voxels, each either empty or filled with a color
+///
+/// Voxel `(x, y, z)` fills the unit cube from `(x, y, z)` to `(x + 1, y + 1, z + 1)`, so the whole
+/// grid spans from the origin to its size. Rays step through the voxels along their path (3D-DDA),
+/// so the cost depends on the grid's resolution rather than on how many voxels are filled.
+/// Neighbouring filled voxels form one solid, only intersected where it meets empty space.
+///
+/// ```
+/// use ray_tracer_challenge::{Color, Point, Ray, Shape, Vector, VoxelGrid};
+///
+/// let mut grid = VoxelGrid::new(3, 1, 1);
+/// grid.set(0, 0, 0, Some(Color::new(1.0, 0.0, 0.0)));
+/// grid.set(2, 0, 0, Some(Color::new(0.0, 0.0, 1.0)));
+///
+/// let xs = grid.intersect(&Ray::new(Point::new(-5.0, 0.5, 0.5), Vector::new(1.0, 0.0, 0.0)));
+/// assert_eq!(xs.iter().map(|i| i.t).collect::<Vec<_>>(), vec![5.0, 6.0, 7.0, 8.0]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct VoxelGrid {
+    data: ShapeData,
+    size: [usize; 3],
+    voxels: Vec<Option<Color>>,
+}
+
+impl VoxelGrid {
+    /// Empty grid of `width` by `height` by `depth` voxels
+    ///
+    /// # Panics
+    ///
+    /// If any of the dimensions is zero.
+    pub fn new(width: usize, height: usize, depth: usize) -> Self {
+        assert!(width > 0 && height > 0 && depth > 0, "voxel grid must not be empty");
+
+        Self {
+            data: Default::default(),
+            size: [width, height, depth],
+            voxels: vec![None; width * height * depth],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.size[0]
+    }
+
+    pub fn height(&self) -> usize {
+        self.size[1]
+    }
+
+    pub fn depth(&self) -> usize {
+        self.size[2]
+    }
+
+    fn index(&self, x: usize, y: usize, z: usize) -> Option<usize> {
+        let [w, h, d] = self.size;
+
+        (x < w && y < h && z < d).then(|| (z * h + y) * w + x)
+    }
+
+    /// Color of a voxel, or `None` if it is empty or outside the grid
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<Color> {
+        self.index(x, y, z).and_then(|i| self.voxels[i])
+    }
+
+    /// Fill a voxel with a color, or empty it with `None`
+    ///
+    /// # Panics
+    ///
+    /// If the voxel is outside the grid.
+    pub fn set(&mut self, x: usize, y: usize, z: usize, color: Option<Color>) {
+        let i = self.index(x, y, z).expect("voxel outside the grid");
+        self.voxels[i] = color;
+    }
+
+    // Voxel at signed coordinates, which may be outside the grid
+    fn voxel(&self, v: [i64; 3]) -> Option<Color> {
+        if v.iter().any(|&c| c < 0) {
+            return None;
+        }
+
+        self.get(v[0] as usize, v[1] as usize, v[2] as usize)
+    }
+
+    // Axis of the voxel face an object space surface point lies on, the voxel on its positive side,
+    // and whether the filled voxel is on that side
+    fn face_at(&self, point: &Point) -> (usize, [i64; 3], bool) {
+        let p = [point.x, point.y, point.z];
+        let axis = (0..3)
+            .min_by(|&a, &b| (p[a] - p[a].round()).abs().total_cmp(&(p[b] - p[b].round()).abs()))
+            .unwrap();
+
+        let mut above = [p[0].floor() as i64, p[1].floor() as i64, p[2].floor() as i64];
+        above[axis] = p[axis].round() as i64;
+
+        let mut below = above;
+        below[axis] -= 1;
+
+        (axis, above, self.voxel(above).is_some() && self.voxel(below).is_none())
+    }
+}
+
+impl Shape for VoxelGrid {
+    fn data(&self) -> &ShapeData {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut ShapeData {
+        &mut self.data
+    }
+
+    fn local_intersect<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a>) {
+        let bounds = self.bounds();
+        let (t_enter, t_exit) = match intersect_box(ray, &bounds.min, &bounds.max) {
+            Some(range) => range,
+            None => return,
+        };
+
+        let start = ray.position(t_enter);
+        let o = [ray.origin.x, ray.origin.y, ray.origin.z];
+        let d = [ray.direction.x, ray.direction.y, ray.direction.z];
+        let p = [start.x, start.y, start.z];
+
+        let mut voxel = [0i64; 3];
+        let mut step = [0i64; 3];
+        let mut t_max = [f64::INFINITY; 3];
+        let mut t_delta = [f64::INFINITY; 3];
+
+        for a in 0..3 {
+            voxel[a] = (p[a].floor() as i64).clamp(0, self.size[a] as i64 - 1);
+
+            if d[a] > 0.0 {
+                step[a] = 1;
+                t_max[a] = ((voxel[a] + 1) as f64 - o[a]) / d[a];
+                t_delta[a] = 1.0 / d[a];
+            } else if d[a] < 0.0 {
+                step[a] = -1;
+                t_max[a] = (voxel[a] as f64 - o[a]) / d[a];
+                t_delta[a] = -1.0 / d[a];
+            }
+        }
+
+        let mut t = t_enter;
+        let mut inside = false;
+        loop {
+            let filled = self.voxel(voxel).is_some();
+            if filled != inside {
+                xs.push(Intersection::new(t, self));
+                inside = filled;
+            }
+
+            let a = (0..3).min_by(|&a, &b| t_max[a].total_cmp(&t_max[b])).unwrap();
+            t = t_max[a];
+            voxel[a] += step[a];
+
+            if t >= t_exit || voxel[a] < 0 || voxel[a] >= self.size[a] as i64 {
+                break;
+            }
+
+            t_max[a] += t_delta[a];
+        }
+
+        if inside {
+            xs.push(Intersection::new(t_exit, self));
+        }
+    }
+
+    fn local_normal_at(&self, point: &Point) -> Vector {
+        let (axis, _, filled_above) = self.face_at(point);
+        let sign = if filled_above { -1.0 } else { 1.0 };
+
+        let mut n = [0.0; 3];
+        n[axis] = sign;
+        Vector::new(n[0], n[1], n[2])
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let [w, h, d] = self.size;
+
+        BoundingBox::new(Point::zero(), Point::new(w as f64, h as f64, d as f64))
+    }
+
+    fn material_at(&self, point: &Point) -> Material {
+        let (axis, above, filled_above) = self.face_at(&self.world_to_object(point));
+
+        let mut voxel = above;
+        if !filled_above {
+            voxel[axis] -= 1;
+        }
+
+        match self.voxel(voxel) {
+            Some(color) => Material { color, ..*self.material() },
+            None => *self.material(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, Point, Ray, Shape, Transform, Vector, VoxelGrid};
+
+    fn ts(grid: &VoxelGrid, origin: Point, direction: Vector) -> Vec<f64> {
+        grid.intersect(&Ray::new(origin, direction)).iter().map(|i| i.t).collect()
+    }
+
+    // Deterministic pseudo-random sequence in 0..1 (xorshift)
+    fn noise(seed: &mut u64) -> f64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        (*seed >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    #[test]
+    fn single_voxel_is_a_unit_cube() {
+        let mut g = VoxelGrid::new(1, 1, 1);
+        assert!(ts(&g, Point::new(0.5, 0.5, -5.0), Vector::new(0.0, 0.0, 1.0)).is_empty());
+
+        g.set(0, 0, 0, Some(Color::one()));
+        assert_eq!(ts(&g, Point::new(0.5, 0.5, -5.0), Vector::new(0.0, 0.0, 1.0)), vec![5.0, 6.0]);
+        assert_eq!(ts(&g, Point::new(0.5, 0.5, 0.5), Vector::new(0.0, -1.0, 0.0)), vec![-0.5, 0.5]);
+        assert!(ts(&g, Point::new(1.5, 0.5, -5.0), Vector::new(0.0, 0.0, 1.0)).is_empty());
+    }
+
+    #[test]
+    fn neighbouring_voxels_form_one_solid() {
+        let mut g = VoxelGrid::new(3, 2, 1);
+        g.set(0, 0, 0, Some(Color::one()));
+        g.set(1, 0, 0, Some(Color::one()));
+
+        assert_eq!(ts(&g, Point::new(-5.0, 0.5, 0.5), Vector::new(1.0, 0.0, 0.0)), vec![5.0, 7.0]);
+        assert_eq!(ts(&g, Point::new(8.0, 0.5, 0.5), Vector::new(-1.0, 0.0, 0.0)), vec![6.0, 8.0]);
+        assert!(ts(&g, Point::new(-5.0, 1.5, 0.5), Vector::new(1.0, 0.0, 0.0)).is_empty());
+    }
+
+    #[test]
+    fn oblique_rays_alternate_between_filled_and_empty() {
+        let mut seed = 0x2545_f491_4f6c_dd1d;
+        let mut g = VoxelGrid::new(6, 5, 4);
+        for z in 0..4 {
+            for y in 0..5 {
+                for x in 0..6 {
+                    if noise(&mut seed) < 0.4 {
+                        g.set(x, y, z, Some(Color::one()));
+                    }
+                }
+            }
+        }
+        let filled = |p: Point| {
+            g.get(p.x.floor() as usize, p.y.floor() as usize, p.z.floor() as usize).is_some()
+        };
+
+        for _ in 0..200 {
+            let origin = Point::new(noise(&mut seed) * 6.0, noise(&mut seed) * 5.0, -3.0);
+            let target = Point::new(noise(&mut seed) * 6.0, noise(&mut seed) * 5.0, 4.0);
+            let r = Ray::new(origin, target - origin);
+            let xs = ts(&g, origin, target - origin);
+
+            assert_eq!(xs.len() % 2, 0);
+            for (i, w) in xs.windows(2).enumerate() {
+                let mid = r.position(0.5 * (w[0] + w[1]));
+                assert_eq!(filled(mid), i % 2 == 0, "{:?} between {} and {}", r, w[0], w[1]);
+            }
+        }
+    }
+
+    #[test]
+    fn normals_face_the_empty_side() {
+        let mut g = VoxelGrid::new(2, 1, 1);
+        g.set(0, 0, 0, Some(Color::one()));
+
+        assert_eq!(g.normal_at(&Point::new(0.5, 0.5, 0.0)), Vector::new(0.0, 0.0, -1.0));
+        assert_eq!(g.normal_at(&Point::new(0.5, 1.0, 0.5)), Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(g.normal_at(&Point::new(0.0, 0.3, 0.6)), Vector::new(-1.0, 0.0, 0.0));
+        assert_eq!(g.normal_at(&Point::new(1.0, 0.3, 0.6)), Vector::new(1.0, 0.0, 0.0));
+
+        g.set(0, 0, 0, None);
+        g.set(1, 0, 0, Some(Color::one()));
+        assert_eq!(g.normal_at(&Point::new(1.0, 0.3, 0.6)), Vector::new(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn hits_take_the_color_of_their_voxel() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let blue = Color::new(0.0, 0.0, 1.0);
+        let mut g = VoxelGrid::new(2, 1, 1).with_transform(Transform::scaling(2.0, 2.0, 2.0));
+        g.set(0, 0, 0, Some(red));
+        g.set(1, 0, 0, Some(blue));
+
+        let r = Ray::new(Point::new(1.0, 1.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = g.intersect(&r);
+        assert_eq!(g.material_at(&r.position(xs[0].t)).color, red);
+
+        let r = Ray::new(Point::new(10.0, 1.0, 1.0), Vector::new(-1.0, 0.0, 0.0));
+        let xs = g.intersect(&r);
+        assert_eq!(g.material_at(&r.position(xs[0].t)).color, blue);
+    }
+
+    #[test]
+    #[should_panic]
+    fn setting_outside_the_grid_panics() {
+        VoxelGrid::new(2, 2, 2).set(0, 2, 0, None);
+    }
+}